@@ -0,0 +1,216 @@
+extern crate alloc;
+use alloc::boxed::Box;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Bit, Bits};
+
+#[cfg(test)]
+mod atomic_bits_tests;
+
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// A fixed length bitmap that can be read and written from several threads
+/// at once.
+///
+/// Bit `i` lives in bit `i % usize::BITS` of word `i / usize::BITS`.  Every
+/// operation uses the ordering given at construction (`SeqCst` by default)
+/// for its read-modify-write, and the matching load ordering for reads.
+#[derive(Debug)]
+pub struct AtomicBits {
+    words: Box<[AtomicUsize]>,
+    len: usize,
+    ordering: Ordering,
+}
+
+impl AtomicBits {
+    /// `len` clear bits using `SeqCst` ordering.
+    #[inline]
+    #[must_use] pub fn new(len: usize) -> Self {
+        Self::with_ordering(len, Ordering::SeqCst)
+    }
+
+    /// `len` clear bits using `ordering` for every operation.
+    #[must_use] pub fn with_ordering(len: usize, ordering: Ordering) -> Self {
+        let words = (0..(len + WORD_BITS - 1) / WORD_BITS)
+            .map(|_| AtomicUsize::new(0))
+            .collect();
+        Self {
+            words,
+            len,
+            ordering,
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    #[must_use] pub fn ordering(&self) -> Ordering {
+        self.ordering
+    }
+
+    #[inline]
+    #[must_use] pub fn get(&self, index: usize) -> Bit {
+        let (word, mask) = self.locate(index);
+        Bit(word.load(self.load_ordering()) & mask != 0)
+    }
+
+    /// Sets the bit at `index`, returning its previous value.
+    #[inline]
+    pub fn fetch_set(&self, index: usize) -> Bit {
+        let (word, mask) = self.locate(index);
+        Bit(word.fetch_or(mask, self.ordering) & mask != 0)
+    }
+
+    /// Clears the bit at `index`, returning its previous value.
+    #[inline]
+    pub fn fetch_clear(&self, index: usize) -> Bit {
+        let (word, mask) = self.locate(index);
+        Bit(word.fetch_and(!mask, self.ordering) & mask != 0)
+    }
+
+    /// Flips the bit at `index`, returning its previous value.
+    #[inline]
+    pub fn fetch_toggle(&self, index: usize) -> Bit {
+        let (word, mask) = self.locate(index);
+        Bit(word.fetch_xor(mask, self.ordering) & mask != 0)
+    }
+
+    /// Sets the bit at `index`, returning whether it was already set.  The
+    /// caller that gets `false` is the one that set it.
+    #[inline]
+    pub fn test_and_set(&self, index: usize) -> bool {
+        self.fetch_set(index).0
+    }
+
+    /// Sets the lowest clear bit and returns its index, or `None` when
+    /// every bit is set.  Bits claimed concurrently by other threads are
+    /// skipped, so each index is handed to exactly one caller.
+    #[inline]
+    pub fn claim_first_zero(&self) -> Option<usize> {
+        self.claim_first_zero_from(0)
+    }
+
+    /// Like `claim_first_zero()`, ignoring the bits before `start`.
+    pub fn claim_first_zero_from(&self, start: usize) -> Option<usize> {
+        if start >= self.len {
+            return None
+        }
+        for (word_index, word) in self.words.iter().enumerate().skip(start / WORD_BITS) {
+            let mut valid = self.valid_mask(word_index);
+            if word_index == start / WORD_BITS {
+                valid &= usize::MAX << (start % WORD_BITS);
+            }
+            let mut current = word.load(self.load_ordering());
+            while !current & valid != 0 {
+                let mask = 1 << (!current & valid).trailing_zeros();
+                match word.compare_exchange_weak(
+                    current,
+                    current | mask,
+                    self.ordering,
+                    self.load_ordering(),
+                ) {
+                    Ok(_) => return Some(word_index * WORD_BITS + mask.trailing_zeros() as usize),
+                    Err(actual) => current = actual,
+                }
+            }
+        }
+        None
+    }
+
+    #[must_use] pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.load(self.load_ordering()).count_ones() as usize)
+            .sum()
+    }
+
+    /// Clears every bit.
+    #[inline]
+    pub fn clear(&self) {
+        for word in self.words.iter() {
+            word.store(0, self.store_ordering());
+        }
+    }
+
+    /// Copies the bits into a `Bits`.  Each word is read atomically, but
+    /// writes racing with the copy may be seen in some words and not
+    /// others.
+    #[must_use] pub fn snapshot(&self) -> Bits {
+        let mut bits = Bits::zeroed(self.len);
+        for (word_index, word) in self.words.iter().enumerate() {
+            let mut value = word.load(self.load_ordering());
+            while value != 0 {
+                bits.set(word_index * WORD_BITS + value.trailing_zeros() as usize, Bit(true));
+                value &= value - 1;
+            }
+        }
+        bits
+    }
+
+    #[inline]
+    fn locate(&self, index: usize) -> (&AtomicUsize, usize) {
+        assert!(
+            index < self.len,
+            "Index {index} out of range for AtomicBits of length {}",
+            self.len
+        );
+        (&self.words[index / WORD_BITS], 1 << (index % WORD_BITS))
+    }
+
+    /// The bits of word `word_index` that lie within `len`.
+    #[inline]
+    fn valid_mask(&self, word_index: usize) -> usize {
+        let bits_in_word = (self.len - word_index * WORD_BITS).min(WORD_BITS);
+        if bits_in_word == WORD_BITS {
+            usize::MAX
+        } else {
+            (1 << bits_in_word) - 1
+        }
+    }
+
+    #[inline]
+    fn load_ordering(&self) -> Ordering {
+        match self.ordering {
+            Ordering::Release | Ordering::Relaxed => Ordering::Relaxed,
+            Ordering::Acquire | Ordering::AcqRel => Ordering::Acquire,
+            _ => Ordering::SeqCst,
+        }
+    }
+
+    #[inline]
+    fn store_ordering(&self) -> Ordering {
+        match self.ordering {
+            Ordering::Acquire | Ordering::Relaxed => Ordering::Relaxed,
+            Ordering::Release | Ordering::AcqRel => Ordering::Release,
+            _ => Ordering::SeqCst,
+        }
+    }
+}
+
+impl From<&Bits> for AtomicBits {
+    #[inline]
+    fn from(bits: &Bits) -> Self {
+        let atomic_bits = Self::new(bits.len());
+        for (index, bit) in bits.iter().enumerate() {
+            if bit.0 {
+                atomic_bits.fetch_set(index);
+            }
+        }
+        atomic_bits
+    }
+}
+
+impl From<&AtomicBits> for Bits {
+    #[inline]
+    fn from(atomic_bits: &AtomicBits) -> Bits {
+        atomic_bits.snapshot()
+    }
+}
@@ -0,0 +1,63 @@
+use crate as bitman;
+use bitman::{AtomicBits, Bit, Bits};
+use core::sync::atomic::Ordering;
+use proptest::prelude::*;
+use std::thread;
+
+#[test]
+fn fetch_operations_return_previous_values() {
+    let atomic_bits = AtomicBits::new(70);
+    assert_eq!(atomic_bits.fetch_set(65), Bit(false));
+    assert_eq!(atomic_bits.fetch_set(65), Bit(true));
+    assert_eq!(atomic_bits.fetch_toggle(3), Bit(false));
+    assert_eq!(atomic_bits.get(3), Bit(true));
+    assert_eq!(atomic_bits.fetch_clear(3), Bit(true));
+    assert_eq!(atomic_bits.fetch_clear(3), Bit(false));
+    assert!(!atomic_bits.test_and_set(0));
+    assert!(atomic_bits.test_and_set(0));
+    assert_eq!(atomic_bits.count_ones(), 2);
+    atomic_bits.clear();
+    assert_eq!(atomic_bits.count_ones(), 0);
+}
+
+#[test]
+fn claim_first_zero_stops_at_len() {
+    let atomic_bits = AtomicBits::with_ordering(3, Ordering::AcqRel);
+    assert_eq!(atomic_bits.ordering(), Ordering::AcqRel);
+    atomic_bits.fetch_set(1);
+    assert_eq!(atomic_bits.claim_first_zero(), Some(0));
+    assert_eq!(atomic_bits.claim_first_zero(), Some(2));
+    assert_eq!(atomic_bits.claim_first_zero(), None);
+    assert_eq!(AtomicBits::new(0).claim_first_zero(), None);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn rejects_indices_past_len() {
+    let _ = AtomicBits::new(8).fetch_set(8);
+}
+
+#[test]
+fn concurrent_claims_are_unique() {
+    let atomic_bits = AtomicBits::with_ordering(1_000, Ordering::Relaxed);
+    let claimed: Vec<usize> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| std::iter::from_fn(|| atomic_bits.claim_first_zero()).collect::<Vec<usize>>()))
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+    let mut sorted = claimed.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..1_000).collect::<Vec<usize>>());
+    assert_eq!(atomic_bits.snapshot(), Bits::new(&[Bit(true); 1_000]));
+}
+
+proptest! {
+    #[test]
+    fn snapshot_round_trips_bits(bytes in proptest::collection::vec(any::<u8>(), 0..40)) {
+        let bits = Bits::from_be_bytes(&bytes);
+        let atomic_bits = AtomicBits::from(&bits);
+        prop_assert_eq!(Bits::from(&atomic_bits), bits.clone());
+        prop_assert_eq!(atomic_bits.count_ones(), bits.count_ones());
+    }
+}
@@ -10,11 +10,14 @@ use crate::{BitMan, Bits};
 use num_traits::{CheckedShl, One, Zero};
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod bit_tests;
 #[cfg(test)]
+#[allow(unused_imports)]
 pub use bit_tests::*;
 
 #[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+#[repr(transparent)]
 pub struct Bit(pub bool);
 
 impl Deref for Bit {
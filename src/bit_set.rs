@@ -0,0 +1,266 @@
+extern crate alloc;
+use alloc::vec;
+use core::hash::{Hash, Hasher};
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+
+use crate::{Bit, BitMan, BitPositions, Bits};
+
+#[cfg(test)]
+mod bit_set_tests;
+
+/// A set of `usize` values kept as a `Bits` in which bit `value` is set for
+/// every member.  The underlying `Bits` grows as larger values are inserted.
+#[derive(Debug, Default, Clone)]
+pub struct BitSet {
+    bits: Bits,
+}
+
+impl BitSet {
+    #[inline]
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty set that holds values below `capacity` without growing.
+    #[inline]
+    #[must_use] pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bits: Bits::new(&vec![Bit(false); capacity]),
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    #[inline]
+    #[must_use] pub fn into_bits(self) -> Bits {
+        self.bits
+    }
+
+    /// Number of members.
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.bits.first_one().is_none()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    #[inline]
+    #[must_use] pub fn contains(&self, value: usize) -> bool {
+        self.bits.get(value).map_or(false, |bit| bit.0)
+    }
+
+    /// Adds `value`, returning whether it was not already a member.
+    #[inline]
+    pub fn insert(&mut self, value: usize) -> bool {
+        if value >= self.bits.len() {
+            self.bits.resize(value + 1, Bit(false));
+        }
+        let newly_inserted = !self.bits[value].0;
+        self.bits.set(value, Bit(true));
+        newly_inserted
+    }
+
+    /// Removes `value`, returning whether it was a member.
+    #[inline]
+    pub fn remove(&mut self, value: usize) -> bool {
+        let was_member = self.contains(value);
+        if was_member {
+            self.bits.set(value, Bit(false));
+        }
+        was_member
+    }
+
+    /// Members in increasing order.
+    #[inline]
+    #[must_use] pub fn iter(&self) -> BitPositions<'_> {
+        self.bits.iter_ones()
+    }
+
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        if other.bits.len() > self.bits.len() {
+            self.bits.resize(other.bits.len(), Bit(false));
+        }
+        for (word, other_word) in self.bits.words_mut().iter_mut().zip(other.bits.words()) {
+            *word |= *other_word;
+        }
+    }
+
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.bits.truncate(other.bits.len());
+        for (word, other_word) in self.bits.words_mut().iter_mut().zip(other.bits.words()) {
+            *word &= *other_word;
+        }
+    }
+
+    #[inline]
+    pub fn difference_with(&mut self, other: &Self) {
+        for (word, other_word) in self.bits.words_mut().iter_mut().zip(other.bits.words()) {
+            *word &= !*other_word;
+        }
+    }
+
+    #[inline]
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        if other.bits.len() > self.bits.len() {
+            self.bits.resize(other.bits.len(), Bit(false));
+        }
+        for (word, other_word) in self.bits.words_mut().iter_mut().zip(other.bits.words()) {
+            *word ^= *other_word;
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn union(&self, other: &Self) -> Self {
+        let mut output_value = self.clone();
+        output_value.union_with(other);
+        output_value
+    }
+
+    #[inline]
+    #[must_use] pub fn intersection(&self, other: &Self) -> Self {
+        let mut output_value = self.clone();
+        output_value.intersect_with(other);
+        output_value
+    }
+
+    #[inline]
+    #[must_use] pub fn difference(&self, other: &Self) -> Self {
+        let mut output_value = self.clone();
+        output_value.difference_with(other);
+        output_value
+    }
+
+    #[inline]
+    #[must_use] pub fn symmetric_difference(&self, other: &Self) -> Self {
+        let mut output_value = self.clone();
+        output_value.symmetric_difference_with(other);
+        output_value
+    }
+
+    #[inline]
+    #[must_use] pub fn is_subset(&self, other: &Self) -> bool {
+        self.iter().all(|value| other.contains(value))
+    }
+
+    #[inline]
+    #[must_use] pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    #[inline]
+    #[must_use] pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.bits
+            .words()
+            .iter()
+            .zip(other.bits.words())
+            .all(|(word, other_word)| word & other_word == 0)
+    }
+}
+
+impl From<Bits> for BitSet {
+    #[inline]
+    fn from(bits: Bits) -> Self {
+        Self { bits }
+    }
+}
+
+impl PartialEq for BitSet {
+    /// Sets are equal when they have the same members, whatever the length
+    /// of their underlying `Bits`.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let (words, other_words) = (self.bits.words(), other.bits.words());
+        let common_len = words.len().min(other_words.len());
+        words[..common_len] == other_words[..common_len]
+            && words[common_len..].iter().all(|&word| word == 0)
+            && other_words[common_len..].iter().all(|&word| word == 0)
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    #[inline]
+    fn hash<State: Hasher>(&self, state: &mut State) {
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    #[inline]
+    fn from_iter<Values: IntoIterator<Item = usize>>(values: Values) -> Self {
+        let mut bit_set = Self::new();
+        bit_set.extend(values);
+        bit_set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    #[inline]
+    fn extend<Values: IntoIterator<Item = usize>>(&mut self, values: Values) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = BitPositions<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> BitSet {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> BitSet {
+        self.intersection(rhs)
+    }
+}
+
+impl BitXor for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> BitSet {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl Sub for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> BitSet {
+        self.difference(rhs)
+    }
+}
@@ -0,0 +1,87 @@
+use crate as bitman;
+use bitman::{BitSet, Bits};
+use proptest::prelude::*;
+use std::collections::BTreeSet;
+
+#[test]
+fn insert_grows_and_reports_new_members() {
+    let mut bit_set = BitSet::new();
+    assert!(bit_set.is_empty());
+    assert!(bit_set.insert(100));
+    assert!(!bit_set.insert(100));
+    assert!(bit_set.insert(3));
+    assert_eq!(bit_set.as_bits().len(), 101);
+    assert_eq!(bit_set.len(), 2);
+    assert!(bit_set.contains(3));
+    assert!(!bit_set.contains(4));
+    assert!(!bit_set.contains(1000));
+}
+
+#[test]
+fn remove_reports_former_members() {
+    let mut bit_set: BitSet = [1, 5, 9].into_iter().collect();
+    assert!(bit_set.remove(5));
+    assert!(!bit_set.remove(5));
+    assert!(!bit_set.remove(500));
+    assert_eq!(bit_set.iter().collect::<Vec<usize>>(), vec![1, 9]);
+    bit_set.clear();
+    assert!(bit_set.is_empty());
+}
+
+#[test]
+fn set_operations_on_sets_of_different_lengths() {
+    let small: BitSet = [1, 2, 3].into_iter().collect();
+    let large: BitSet = [3, 4, 200].into_iter().collect();
+    assert_eq!((&small | &large).iter().collect::<Vec<usize>>(), vec![1, 2, 3, 4, 200]);
+    assert_eq!((&small & &large).iter().collect::<Vec<usize>>(), vec![3]);
+    assert_eq!((&small - &large).iter().collect::<Vec<usize>>(), vec![1, 2]);
+    assert_eq!((&large - &small).iter().collect::<Vec<usize>>(), vec![4, 200]);
+    assert_eq!((&small ^ &large).iter().collect::<Vec<usize>>(), vec![1, 2, 4, 200]);
+}
+
+#[test]
+fn subset_and_disjoint_checks() {
+    let small: BitSet = [2, 3].into_iter().collect();
+    let large: BitSet = [1, 2, 3, 90].into_iter().collect();
+    let other: BitSet = [4, 90].into_iter().collect();
+    assert!(small.is_subset(&large));
+    assert!(large.is_superset(&small));
+    assert!(!large.is_subset(&small));
+    assert!(small.is_disjoint(&other));
+    assert!(!large.is_disjoint(&other));
+    assert!(BitSet::new().is_subset(&small));
+}
+
+#[test]
+fn equality_ignores_trailing_capacity() {
+    let mut wide = BitSet::with_capacity(1000);
+    wide.insert(7);
+    let narrow: BitSet = [7].into_iter().collect();
+    assert_eq!(wide, narrow);
+    assert_ne!(wide, BitSet::new());
+}
+
+#[test]
+fn wraps_existing_bits() {
+    let bit_set = BitSet::from(Bits::from_be_bytes(&[0b0100_0001]));
+    assert_eq!(bit_set.iter().collect::<Vec<usize>>(), vec![1, 7]);
+    assert_eq!(bit_set.into_bits(), Bits::from_be_bytes(&[0b0100_0001]));
+}
+
+proptest! {
+    #[test]
+    fn matches_btree_set(
+        lhs in proptest::collection::btree_set(0usize..300, 0..40),
+        rhs in proptest::collection::btree_set(0usize..300, 0..40),
+    ) {
+        let lhs_set: BitSet = lhs.iter().copied().collect();
+        let rhs_set: BitSet = rhs.iter().copied().collect();
+        prop_assert_eq!(lhs_set.union(&rhs_set).iter().collect::<BTreeSet<usize>>(), lhs.union(&rhs).copied().collect::<BTreeSet<usize>>());
+        prop_assert_eq!(lhs_set.intersection(&rhs_set).iter().collect::<BTreeSet<usize>>(), lhs.intersection(&rhs).copied().collect::<BTreeSet<usize>>());
+        prop_assert_eq!(lhs_set.difference(&rhs_set).iter().collect::<BTreeSet<usize>>(), lhs.difference(&rhs).copied().collect::<BTreeSet<usize>>());
+        prop_assert_eq!(lhs_set.symmetric_difference(&rhs_set).iter().collect::<BTreeSet<usize>>(), lhs.symmetric_difference(&rhs).copied().collect::<BTreeSet<usize>>());
+        prop_assert_eq!(lhs_set.is_subset(&rhs_set), lhs.is_subset(&rhs));
+        prop_assert_eq!(lhs_set.is_disjoint(&rhs_set), lhs.is_disjoint(&rhs));
+        prop_assert_eq!(lhs_set.len(), lhs.len());
+    }
+}
@@ -0,0 +1,239 @@
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::vec;
+use core::fmt::{self, Debug};
+use core::mem::ManuallyDrop;
+
+use crate::Bit;
+
+#[cfg(test)]
+mod bit_storage_tests;
+
+/// Bits kept in place before spilling to the heap: two 64 bit words.
+pub(crate) const INLINE_BITS: usize = 2 * 64;
+
+const INLINE_WORDS: usize = INLINE_BITS / 64;
+
+/// The words themselves.  Which field is live follows from the length, so
+/// the storage needs no separate tag.
+union Words {
+    inline: [u64; INLINE_WORDS],
+    heap: ManuallyDrop<Box<[u64]>>,
+}
+
+/// The storage behind `Bits`: bits packed into 64 bit words, bit `i` at bit
+/// `i % 64` of word `i / 64`.
+///
+/// Up to `INLINE_BITS` bits are held in place and longer ones on the heap,
+/// so the storage is a length and two words, the size of a `Vec`.  Bits
+/// past the length are always zero, so equal bits have equal words.
+pub(crate) struct BitStorage {
+    len: usize,
+    words: Words,
+}
+
+impl BitStorage {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            len: 0,
+            words: Words {
+                inline: [0; INLINE_WORDS],
+            },
+        }
+    }
+
+    /// `len` zero bits.
+    #[inline]
+    pub(crate) fn zeroed(len: usize) -> Self {
+        let mut storage = Self::new();
+        storage.set_len(len);
+        storage
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub(crate) fn is_inline(&self) -> bool {
+        self.len <= INLINE_BITS
+    }
+
+    /// Words the heap allocation has room for, or `INLINE_WORDS` in place.
+    #[inline]
+    fn capacity_words(&self) -> usize {
+        if self.is_inline() {
+            INLINE_WORDS
+        } else {
+            // SAFETY: the heap field is live whenever the bits are not inline.
+            unsafe { self.words.heap.len() }
+        }
+    }
+
+    /// The words holding the bits, the last one zero padded.
+    #[inline]
+    pub(crate) fn words(&self) -> &[u64] {
+        let word_len = word_len(self.len);
+        if self.is_inline() {
+            // SAFETY: the inline field is live while the bits are inline.
+            unsafe { &self.words.inline[..word_len] }
+        } else {
+            // SAFETY: the heap field is live whenever the bits are not inline.
+            unsafe { &self.words.heap[..word_len] }
+        }
+    }
+
+    /// Mutable `words()`.  Callers must leave the padding bits zero.
+    #[inline]
+    pub(crate) fn words_mut(&mut self) -> &mut [u64] {
+        let word_len = word_len(self.len);
+        if self.is_inline() {
+            // SAFETY: the inline field is live while the bits are inline.
+            unsafe { &mut self.words.inline[..word_len] }
+        } else {
+            // SAFETY: the heap field is live whenever the bits are not inline.
+            unsafe { &mut (*self.words.heap)[..word_len] }
+        }
+    }
+
+    /// Zeroes the bits of the last word past the length, after writing
+    /// whole words.
+    #[inline]
+    pub(crate) fn clear_padding(&mut self) {
+        let len = self.len;
+        if len % 64 != 0 {
+            let words = self.words_mut();
+            let last = words.len() - 1;
+            words[last] &= u64::MAX >> (64 - len % 64);
+        }
+    }
+
+    /// Makes room for `additional` more bits.  Only allocates once the bits
+    /// are on the heap, as inline storage cannot hold a spare allocation.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        if !self.is_inline() {
+            self.grow(word_len(self.len + additional));
+        }
+    }
+
+    /// Changes the length, zeroing bits cut off and adding zero bits.
+    pub(crate) fn set_len(&mut self, new_len: usize) {
+        let (old_len, old_words, new_words) = (self.len, word_len(self.len), word_len(new_len));
+        if new_len < old_len {
+            let words = self.words_mut();
+            words[new_words..old_words].fill(0);
+            if new_len % 64 != 0 {
+                words[new_words - 1] &= u64::MAX >> (64 - new_len % 64);
+            }
+            if !self.is_inline() && new_len <= INLINE_BITS {
+                let mut inline = [0; INLINE_WORDS];
+                inline.copy_from_slice(&self.words()[..INLINE_WORDS]);
+                // SAFETY: the heap field is live until it is replaced here.
+                unsafe { ManuallyDrop::drop(&mut self.words.heap) };
+                self.words = Words { inline };
+            }
+        } else if new_len > INLINE_BITS {
+            if self.is_inline() {
+                // SAFETY: the inline field is live while the bits are inline.
+                let inline = unsafe { self.words.inline };
+                let mut heap = vec![0; new_words.max(2 * INLINE_WORDS)].into_boxed_slice();
+                heap[..INLINE_WORDS].copy_from_slice(&inline);
+                self.words = Words {
+                    heap: ManuallyDrop::new(heap),
+                };
+                self.len = new_len;
+                return
+            }
+            self.grow(new_words);
+        }
+        self.len = new_len;
+    }
+
+    /// Reallocates heap words so that at least `needed` fit, at least
+    /// doubling them so that pushing stays amortized constant time.
+    fn grow(&mut self, needed: usize) {
+        let capacity = self.capacity_words();
+        if needed <= capacity {
+            return
+        }
+        let mut heap = vec![0; needed.max(2 * capacity)].into_boxed_slice();
+        heap[..capacity].copy_from_slice(
+            // SAFETY: only called while the heap field is live.
+            unsafe { &self.words.heap },
+        );
+        // SAFETY: as above; the old allocation is replaced straight away.
+        unsafe { ManuallyDrop::drop(&mut self.words.heap) };
+        self.words = Words {
+            heap: ManuallyDrop::new(heap),
+        };
+    }
+}
+
+/// Words needed for `len` bits.
+#[inline]
+pub(crate) fn word_len(len: usize) -> usize {
+    (len + 63) / 64
+}
+
+impl Default for BitStorage {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for BitStorage {
+    #[inline]
+    fn clone(&self) -> Self {
+        if self.is_inline() {
+            return Self {
+                len: self.len,
+                // SAFETY: the inline field is live while the bits are inline.
+                words: Words {
+                    inline: unsafe { self.words.inline },
+                },
+            }
+        }
+        Self {
+            len: self.len,
+            words: Words {
+                heap: ManuallyDrop::new(Box::from(self.words())),
+            },
+        }
+    }
+}
+
+impl Drop for BitStorage {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.is_inline() {
+            // SAFETY: the heap field is live whenever the bits are not
+            // inline, and is not used again.
+            unsafe { ManuallyDrop::drop(&mut self.words.heap) };
+        }
+    }
+}
+
+impl From<&[Bit]> for BitStorage {
+    #[inline]
+    fn from(bits: &[Bit]) -> Self {
+        let mut storage = Self::zeroed(bits.len());
+        let words = storage.words_mut();
+        for (index, bit) in bits.iter().enumerate() {
+            words[index / 64] |= u64::from(bit.0) << (index % 64);
+        }
+        storage
+    }
+}
+
+impl Debug for BitStorage {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_list()
+            .entries((0..self.len).map(|index| Bit((self.words()[index / 64] >> (index % 64)) & 1 == 1)))
+            .finish()
+    }
+}
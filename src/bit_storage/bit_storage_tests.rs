@@ -0,0 +1,150 @@
+use crate as bitman;
+use bitman::bit_storage::INLINE_BITS;
+use bitman::{Bit, BitMan, Bits};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Push(bool),
+    Pop,
+    Set(usize, bool),
+    Truncate(usize),
+    Resize(usize, bool),
+    ExtendFromSlice(Vec<bool>),
+    Append(Vec<bool>),
+    Clear,
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        any::<bool>().prop_map(Operation::Push),
+        Just(Operation::Pop),
+        (any::<usize>(), any::<bool>()).prop_map(|(index, bit)| Operation::Set(index, bit)),
+        (0..300usize).prop_map(Operation::Truncate),
+        (0..300usize, any::<bool>()).prop_map(|(len, bit)| Operation::Resize(len, bit)),
+        prop::collection::vec(any::<bool>(), 0..100).prop_map(Operation::ExtendFromSlice),
+        prop::collection::vec(any::<bool>(), 0..200).prop_map(Operation::Append),
+        Just(Operation::Clear),
+    ]
+}
+
+/// Applies `operation` to both, clamping positions to the current length.
+fn apply(bits: &mut Bits, vector: &mut Vec<Bit>, operation: Operation) {
+    let len = vector.len();
+    match operation {
+        Operation::Push(bit) => {
+            bits.push(Bit(bit));
+            vector.push(Bit(bit));
+        }
+        Operation::Pop => assert_eq!(bits.pop(), vector.pop()),
+        Operation::Set(index, bit) => {
+            if len > 0 {
+                bits.set(index % len, Bit(bit));
+                vector[index % len] = Bit(bit);
+            }
+        }
+        Operation::Truncate(new_len) => {
+            bits.truncate(new_len);
+            vector.truncate(new_len);
+        }
+        Operation::Resize(new_len, bit) => {
+            bits.resize(new_len, Bit(bit));
+            vector.resize(new_len, Bit(bit));
+        }
+        Operation::ExtendFromSlice(extra) => {
+            let extra: Vec<Bit> = extra.into_iter().map(Bit).collect();
+            bits.extend_from_slice(&extra);
+            vector.extend_from_slice(&extra);
+        }
+        Operation::Append(extra) => {
+            let extra: Vec<Bit> = extra.into_iter().map(Bit).collect();
+            let mut other = Bits::new(&extra);
+            bits.append(&mut other);
+            assert!(other.is_empty());
+            vector.extend_from_slice(&extra);
+        }
+        Operation::Clear => {
+            bits.clear();
+            vector.clear();
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn bits_behave_like_a_vec_of_bits(operations in prop::collection::vec(operation(), 0..60)) {
+        let mut bits = Bits::new(&[]);
+        let mut vector: Vec<Bit> = Vec::new();
+        for operation in operations {
+            apply(&mut bits, &mut vector, operation);
+            prop_assert_eq!(&bits, &vector[..]);
+            prop_assert_eq!(bits.is_inline(), vector.len() <= INLINE_BITS);
+            let words = bits.words();
+            prop_assert_eq!(words.len(), (vector.len() + 63) / 64);
+            if vector.len() % 64 != 0 {
+                prop_assert_eq!(words[words.len() - 1] >> (vector.len() % 64), 0);
+            }
+        }
+        prop_assert_eq!(bits.to_vec(), vector);
+    }
+
+    #[test]
+    fn bits_stay_inline_up_to_two_words(len in 0..=INLINE_BITS, value in any::<u128>()) {
+        let bits = Bits::new(&vec![Bit(true); len]);
+        prop_assert!(bits.is_inline());
+        prop_assert_eq!(bits.count_ones(), len);
+        prop_assert!(Bits::from(value).is_inline());
+        let value_bits = value.bits();
+        prop_assert!(value_bits.is_inline());
+        for index in 0..128u32 {
+            prop_assert_eq!(value_bits.bit(&index), value.bit(&index));
+        }
+    }
+}
+
+#[test]
+fn bits_are_three_words() {
+    assert_eq!(core::mem::size_of::<Bits>(), 3 * core::mem::size_of::<usize>());
+}
+
+#[test]
+fn bit_storage_spills_past_two_words_and_shrinks_back() {
+    let mut bits = Bits::new(&[Bit(true); INLINE_BITS]);
+    assert!(bits.is_inline());
+    bits.push(Bit(false));
+    assert!(!bits.is_inline());
+    assert_eq!(bits.len(), INLINE_BITS + 1);
+    assert_eq!(bits.last_zero(), Some(INLINE_BITS));
+    assert!(!Bits::new(&[Bit(false); INLINE_BITS + 1]).is_inline());
+    bits.pop();
+    assert!(bits.is_inline());
+    assert_eq!(bits, Bits::new(&[Bit(true); INLINE_BITS]));
+}
+
+#[test]
+fn bits_compare_and_hash_by_value_whatever_their_history() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let fresh = Bits::new(&[Bit(true), Bit(false), Bit(true)]);
+    let mut shrunk = Bits::new(&[Bit(true); INLINE_BITS * 3]);
+    shrunk.truncate(0);
+    shrunk.extend_from_slice(&[Bit(true), Bit(false), Bit(true)]);
+    let mut pushed = Bits::new(&[]);
+    for index in 0..INLINE_BITS * 2 {
+        pushed.push(Bit(index % 2 == 0));
+    }
+    pushed.truncate(3);
+    pushed.set(1, Bit(false));
+    let hash = |bits: &Bits| {
+        let mut hasher = DefaultHasher::new();
+        bits.hash(&mut hasher);
+        hasher.finish()
+    };
+    for bits in [&shrunk, &pushed] {
+        assert_eq!(&fresh, bits);
+        assert_eq!(hash(&fresh), hash(bits));
+        assert_eq!(format!("{fresh:?}"), format!("{bits:?}"));
+    }
+    assert_ne!(Bits::new(&[Bit(false); 3]), Bits::new(&[Bit(false); 4]));
+}
@@ -0,0 +1,341 @@
+extern crate alloc;
+use alloc::vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::scanning::find_first;
+use crate::{AtomicBits, Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod bitmap_allocator_tests;
+
+/// Slots covered by one bit of the summary bitmap.
+const SUMMARY_GROUP_LEN: usize = 64;
+
+/// Where a `BitmapAllocator` starts looking for free slots.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum FitStrategy {
+    /// From the first slot, keeping allocations packed at the start.
+    FirstFit,
+    /// From just past the previous allocation, wrapping around at the end.
+    NextFit,
+}
+
+/// Largest number of consecutive free (clear) slots.
+fn largest_free_run(slots: &Bits) -> usize {
+    slots
+        .iter_runs()
+        .filter(|(bit, _, _)| !bit.0)
+        .map(|(_, _, len)| len)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Share of the free slots lying outside the largest free run.
+fn fragmentation(slots: &Bits) -> f64 {
+    let free = slots.count_zeros();
+    if free == 0 {
+        return 0.0
+    }
+    1.0 - largest_free_run(slots) as f64 / free as f64
+}
+
+/// Hands out slots, or runs of adjacent slots, tracked by one bit each.
+///
+/// With `with_summary()` it also keeps one bit per 64 slots marking groups
+/// that are completely allocated, so searches skip them without scanning.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BitmapAllocator {
+    slots: Bits,
+    summary: Option<Bits>,
+    strategy: FitStrategy,
+    next_fit_start: usize,
+}
+
+impl BitmapAllocator {
+    /// `slot_count` free slots.
+    #[must_use] pub fn new(slot_count: usize, strategy: FitStrategy) -> Self {
+        Self {
+            slots: Bits::new(&vec![Bit(false); slot_count]),
+            summary: None,
+            strategy,
+            next_fit_start: 0,
+        }
+    }
+
+    /// `slot_count` free slots, searched through a summary of full groups.
+    #[must_use] pub fn with_summary(slot_count: usize, strategy: FitStrategy) -> Self {
+        let group_count = (slot_count + SUMMARY_GROUP_LEN - 1) / SUMMARY_GROUP_LEN;
+        let mut allocator = Self::new(slot_count, strategy);
+        allocator.summary = Some(Bits::new(&vec![Bit(false); group_count]));
+        allocator
+    }
+
+    #[inline]
+    #[must_use] pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    #[must_use] pub fn strategy(&self) -> FitStrategy {
+        self.strategy
+    }
+
+    /// The slots, with allocated ones set.
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.slots
+    }
+
+    #[inline]
+    #[must_use] pub fn is_allocated(&self, slot: usize) -> bool {
+        self.slots[slot].0
+    }
+
+    #[inline]
+    #[must_use] pub fn allocated_count(&self) -> usize {
+        self.slots.count_ones()
+    }
+
+    #[inline]
+    #[must_use] pub fn free_count(&self) -> usize {
+        self.slots.count_zeros()
+    }
+
+    #[inline]
+    #[must_use] pub fn largest_free_run(&self) -> usize {
+        largest_free_run(&self.slots)
+    }
+
+    /// Share of the free slots lying outside the largest free run: 0 when
+    /// all free slots are adjacent, approaching 1 as they scatter.
+    #[inline]
+    #[must_use] pub fn fragmentation(&self) -> f64 {
+        fragmentation(&self.slots)
+    }
+
+    /// Allocates one slot.
+    #[inline]
+    pub fn allocate(&mut self) -> Option<usize> {
+        self.allocate_run(1)
+    }
+
+    /// Allocates `slot_count` adjacent slots, returning the first.
+    pub fn allocate_run(&mut self, slot_count: usize) -> Option<usize> {
+        assert!(slot_count > 0, "Cannot allocate an empty run");
+        let capacity = self.capacity();
+        let start = match self.strategy {
+            FitStrategy::FirstFit => self.find_free_run(0, capacity, slot_count)?,
+            FitStrategy::NextFit => {
+                let from = self.next_fit_start;
+                self.find_free_run(from, capacity, slot_count).or_else(|| {
+                    self.find_free_run(0, (from + slot_count - 1).min(capacity), slot_count)
+                })?
+            }
+        };
+        self.mark(start, slot_count, Bit(true));
+        self.next_fit_start = (start + slot_count) % capacity;
+        Some(start)
+    }
+
+    #[inline]
+    pub fn free(&mut self, slot: usize) {
+        self.free_run(slot, 1);
+    }
+
+    /// Frees `slot_count` slots starting at `start`, all of which must be
+    /// allocated.
+    pub fn free_run(&mut self, start: usize, slot_count: usize) {
+        assert!(
+            start + slot_count <= self.capacity() && self.slots.count_zeros_in(start..start + slot_count) == 0,
+            "Slots {start}..{} are not all allocated",
+            start + slot_count
+        );
+        self.mark(start, slot_count, Bit(false));
+    }
+
+    fn mark(&mut self, start: usize, slot_count: usize, bit: Bit) {
+        if slot_count == 0 {
+            return
+        }
+        for slot in start..start + slot_count {
+            self.slots.set(slot, bit);
+        }
+        if let Some(summary) = &mut self.summary {
+            let capacity = self.slots.len();
+            for group in start / SUMMARY_GROUP_LEN..=(start + slot_count - 1) / SUMMARY_GROUP_LEN {
+                let group_start = group * SUMMARY_GROUP_LEN;
+                let group_end = (group_start + SUMMARY_GROUP_LEN).min(capacity);
+                summary.set(group, Bit(self.slots.count_zeros_in(group_start..group_end) == 0));
+            }
+        }
+    }
+
+    /// Start of the first `slot_count` free slots lying within `from..end`.
+    fn find_free_run(&self, from: usize, end: usize, slot_count: usize) -> Option<usize> {
+        let mut start = from;
+        loop {
+            let free = self.first_free(start, end)?;
+            if end - free < slot_count {
+                return None
+            }
+            match find_first(self.slots.words(), free, free + slot_count, true) {
+                Some(taken) => start = taken + 1,
+                None => return Some(free),
+            }
+        }
+    }
+
+    /// First free slot within `from..end`.
+    fn first_free(&self, from: usize, end: usize) -> Option<usize> {
+        let words = self.slots.words();
+        let summary = match &self.summary {
+            Some(summary) => summary,
+            None if from > end || end > self.slots.len() => return None,
+            None => return find_first(words, from, end, false),
+        };
+        let mut position = from;
+        while position < end {
+            let group = position / SUMMARY_GROUP_LEN;
+            let group_end = ((group + 1) * SUMMARY_GROUP_LEN).min(end);
+            if !summary[group].0 {
+                if let Some(free) = find_first(words, position, group_end, false) {
+                    return Some(free)
+                }
+            }
+            position = summary.next_zero_after(group)? * SUMMARY_GROUP_LEN;
+        }
+        None
+    }
+}
+
+/// A `BitmapAllocator` over `AtomicBits`, shareable between threads.
+///
+/// Each slot is claimed with an atomic test-and-set, so no slot is ever
+/// handed out twice.  A run is claimed slot by slot and released again if
+/// another thread takes one of its slots first, so under contention an
+/// allocation may pass over space that a competing allocation gives back.
+#[derive(Debug)]
+pub struct AtomicBitmapAllocator {
+    slots: AtomicBits,
+    strategy: FitStrategy,
+    next_fit_start: AtomicUsize,
+}
+
+impl AtomicBitmapAllocator {
+    #[must_use] pub fn new(slot_count: usize, strategy: FitStrategy) -> Self {
+        Self {
+            slots: AtomicBits::with_ordering(slot_count, Ordering::AcqRel),
+            strategy,
+            next_fit_start: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    #[must_use] pub fn strategy(&self) -> FitStrategy {
+        self.strategy
+    }
+
+    #[inline]
+    #[must_use] pub fn is_allocated(&self, slot: usize) -> bool {
+        self.slots.get(slot).0
+    }
+
+    #[inline]
+    #[must_use] pub fn allocated_count(&self) -> usize {
+        self.slots.count_ones()
+    }
+
+    /// The slots as they are now, with allocated ones set.
+    #[inline]
+    #[must_use] pub fn snapshot(&self) -> Bits {
+        self.slots.snapshot()
+    }
+
+    /// `BitmapAllocator::fragmentation()` of a snapshot.
+    #[inline]
+    #[must_use] pub fn fragmentation(&self) -> f64 {
+        fragmentation(&self.snapshot())
+    }
+
+    /// Allocates one slot.
+    pub fn allocate(&self) -> Option<usize> {
+        let slot = match self.strategy {
+            FitStrategy::FirstFit => self.slots.claim_first_zero()?,
+            FitStrategy::NextFit => self
+                .slots
+                .claim_first_zero_from(self.next_fit_start.load(Ordering::Relaxed))
+                .or_else(|| self.slots.claim_first_zero())?,
+        };
+        self.advance_next_fit(slot, 1);
+        Some(slot)
+    }
+
+    /// Allocates `slot_count` adjacent slots, returning the first.
+    pub fn allocate_run(&self, slot_count: usize) -> Option<usize> {
+        assert!(slot_count > 0, "Cannot allocate an empty run");
+        if slot_count == 1 {
+            return self.allocate()
+        }
+        let capacity = self.capacity();
+        let start = match self.strategy {
+            FitStrategy::FirstFit => self.claim_run(0, capacity, slot_count)?,
+            FitStrategy::NextFit => {
+                let from = self.next_fit_start.load(Ordering::Relaxed);
+                self.claim_run(from, capacity, slot_count).or_else(|| {
+                    self.claim_run(0, (from + slot_count - 1).min(capacity), slot_count)
+                })?
+            }
+        };
+        self.advance_next_fit(start, slot_count);
+        Some(start)
+    }
+
+    #[inline]
+    pub fn free(&self, slot: usize) {
+        self.free_run(slot, 1);
+    }
+
+    /// Frees `slot_count` slots starting at `start`, all of which must be
+    /// allocated.
+    pub fn free_run(&self, start: usize, slot_count: usize) {
+        for slot in start..start + slot_count {
+            assert!(self.slots.fetch_clear(slot).0, "Slot {slot} is not allocated");
+        }
+    }
+
+    #[inline]
+    fn advance_next_fit(&self, start: usize, slot_count: usize) {
+        if self.strategy == FitStrategy::NextFit {
+            self.next_fit_start
+                .store((start + slot_count) % self.capacity(), Ordering::Relaxed);
+        }
+    }
+
+    /// Claims the first `slot_count` free slots lying within `from..end`.
+    fn claim_run(&self, from: usize, end: usize, slot_count: usize) -> Option<usize> {
+        let mut start = from;
+        while start + slot_count <= end {
+            if let Some(used) = (start..start + slot_count).rev().find(|&slot| self.slots.get(slot).0) {
+                start = used + 1;
+                continue
+            }
+            let mut claimed = 0;
+            while claimed < slot_count && !self.slots.test_and_set(start + claimed) {
+                claimed += 1;
+            }
+            if claimed == slot_count {
+                return Some(start)
+            }
+            for slot in start..start + claimed {
+                self.slots.fetch_clear(slot);
+            }
+            start += claimed + 1;
+        }
+        None
+    }
+}
@@ -0,0 +1,126 @@
+use crate as bitman;
+use bitman::{AtomicBitmapAllocator, BitmapAllocator, FitStrategy};
+use proptest::prelude::*;
+use std::thread;
+
+#[test]
+fn first_fit_reuses_the_lowest_hole() {
+    let mut allocator = BitmapAllocator::new(16, FitStrategy::FirstFit);
+    assert_eq!(allocator.allocate_run(4), Some(0));
+    assert_eq!(allocator.allocate_run(4), Some(4));
+    assert_eq!(allocator.allocate(), Some(8));
+    allocator.free_run(0, 4);
+    assert_eq!(allocator.allocate_run(2), Some(0));
+    assert_eq!(allocator.allocate_run(3), Some(9));
+    assert_eq!(allocator.allocate_run(5), None);
+    assert_eq!(allocator.allocated_count(), 10);
+    assert_eq!(allocator.free_count(), 6);
+}
+
+#[test]
+fn next_fit_continues_after_the_last_allocation_and_wraps() {
+    let mut allocator = BitmapAllocator::new(8, FitStrategy::NextFit);
+    assert_eq!(allocator.allocate_run(3), Some(0));
+    allocator.free_run(0, 3);
+    assert_eq!(allocator.allocate_run(3), Some(3));
+    assert_eq!(allocator.allocate_run(3), Some(0));
+    assert_eq!(allocator.allocate(), Some(6));
+    assert_eq!(allocator.allocate(), Some(7));
+    assert_eq!(allocator.allocate(), None);
+}
+
+#[test]
+fn summary_skips_full_groups() {
+    let mut allocator = BitmapAllocator::with_summary(300, FitStrategy::FirstFit);
+    assert_eq!(allocator.allocate_run(200), Some(0));
+    assert_eq!(allocator.allocate_run(64), Some(200));
+    allocator.free(70);
+    assert_eq!(allocator.allocate_run(2), Some(264));
+    assert_eq!(allocator.allocate(), Some(70));
+    assert_eq!(allocator.allocate_run(34), Some(266));
+    assert_eq!(allocator.allocate(), None);
+}
+
+#[test]
+fn reports_fragmentation() {
+    let mut allocator = BitmapAllocator::new(10, FitStrategy::FirstFit);
+    assert!(allocator.fragmentation().abs() < f64::EPSILON);
+    allocator.allocate_run(10);
+    allocator.free_run(1, 2);
+    allocator.free_run(5, 4);
+    assert_eq!(allocator.largest_free_run(), 4);
+    assert!((allocator.fragmentation() - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "not all allocated")]
+fn rejects_freeing_free_slots() {
+    let mut allocator = BitmapAllocator::new(10, FitStrategy::FirstFit);
+    allocator.allocate();
+    allocator.free_run(0, 2);
+}
+
+#[test]
+fn atomic_allocator_hands_out_disjoint_runs() {
+    let allocator = AtomicBitmapAllocator::new(1_024, FitStrategy::NextFit);
+    let starts: Vec<usize> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| std::iter::from_fn(|| allocator.allocate_run(4)).collect::<Vec<usize>>()))
+            .collect();
+        workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+    });
+    let mut slots: Vec<usize> = starts.iter().flat_map(|&start| start..start + 4).collect();
+    slots.sort_unstable();
+    slots.dedup();
+    assert_eq!(slots.len(), starts.len() * 4);
+    assert_eq!(allocator.allocated_count(), slots.len());
+    for start in starts {
+        allocator.free_run(start, 4);
+    }
+    assert_eq!(allocator.allocated_count(), 0);
+    assert!(allocator.fragmentation().abs() < f64::EPSILON);
+}
+
+#[test]
+fn atomic_allocator_follows_the_strategy() {
+    let allocator = AtomicBitmapAllocator::new(8, FitStrategy::FirstFit);
+    assert_eq!(allocator.allocate_run(3), Some(0));
+    assert_eq!(allocator.allocate(), Some(3));
+    allocator.free(1);
+    assert_eq!(allocator.allocate(), Some(1));
+    assert_eq!(allocator.allocate_run(4), Some(4));
+    assert_eq!(allocator.allocate(), None);
+    assert!(allocator.is_allocated(7));
+}
+
+proptest! {
+    #[test]
+    fn allocations_never_overlap(
+        requests in proptest::collection::vec((1usize..20, any::<bool>()), 0..60),
+        use_summary in any::<bool>(),
+        next_fit in any::<bool>(),
+    ) {
+        let strategy = if next_fit { FitStrategy::NextFit } else { FitStrategy::FirstFit };
+        let mut allocator = if use_summary {
+            BitmapAllocator::with_summary(200, strategy)
+        } else {
+            BitmapAllocator::new(200, strategy)
+        };
+        let mut owned = [false; 200];
+        let mut live: Vec<(usize, usize)> = Vec::new();
+        for (slot_count, free_one) in requests {
+            if free_one && !live.is_empty() {
+                let (start, len) = live.remove(slot_count % live.len());
+                allocator.free_run(start, len);
+                owned[start..start + len].iter_mut().for_each(|slot| *slot = false);
+            } else if let Some(start) = allocator.allocate_run(slot_count) {
+                prop_assert!(owned[start..start + slot_count].iter().all(|slot| !slot));
+                owned[start..start + slot_count].iter_mut().for_each(|slot| *slot = true);
+                live.push((start, slot_count));
+            } else if !next_fit {
+                prop_assert!(!owned.windows(slot_count).any(|window| window.iter().all(|slot| !slot)));
+            }
+        }
+        prop_assert_eq!(allocator.allocated_count(), owned.iter().filter(|slot| **slot).count());
+    }
+}
@@ -0,0 +1,319 @@
+extern crate alloc;
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::ops::{BitAnd, BitOr, Not};
+
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod bitmap_index_tests;
+
+/// Returned when a `Predicate` names a column the `BitmapIndex` does not have.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct UnknownColumn {
+    pub name: String,
+}
+
+impl Display for UnknownColumn {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "BitmapIndex has no column named {}", self.name)
+    }
+}
+
+/// How a column's rows are turned into bitmaps.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum ColumnEncoding {
+    /// One bitmap per distinct value marking the rows equal to it.  Cheapest
+    /// for `Equals` and `In`.
+    Equality,
+    /// One bitmap per distinct value marking the rows less than or equal to
+    /// it.  Any comparison or range needs at most two bitmaps.
+    Range,
+}
+
+/// A boolean condition over the columns of a `BitmapIndex`.
+///
+/// Predicates combine with `&`, `|` and `!`, so `a = 3 AND b IN (1, 2)` is
+/// `Predicate::equals("a", 3) & Predicate::is_in("b", [1, 2])`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Predicate {
+    Equals(String, u64),
+    In(String, Vec<u64>),
+    LessThan(String, u64),
+    LessOrEqual(String, u64),
+    GreaterThan(String, u64),
+    GreaterOrEqual(String, u64),
+    /// Inclusive on both ends.
+    Between(String, u64, u64),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    #[inline]
+    #[must_use] pub fn equals(column: &str, value: u64) -> Self {
+        Self::Equals(column.to_owned(), value)
+    }
+
+    #[inline]
+    #[must_use] pub fn is_in<Values: IntoIterator<Item = u64>>(column: &str, values: Values) -> Self {
+        Self::In(column.to_owned(), values.into_iter().collect())
+    }
+
+    #[inline]
+    #[must_use] pub fn less_than(column: &str, value: u64) -> Self {
+        Self::LessThan(column.to_owned(), value)
+    }
+
+    #[inline]
+    #[must_use] pub fn less_or_equal(column: &str, value: u64) -> Self {
+        Self::LessOrEqual(column.to_owned(), value)
+    }
+
+    #[inline]
+    #[must_use] pub fn greater_than(column: &str, value: u64) -> Self {
+        Self::GreaterThan(column.to_owned(), value)
+    }
+
+    #[inline]
+    #[must_use] pub fn greater_or_equal(column: &str, value: u64) -> Self {
+        Self::GreaterOrEqual(column.to_owned(), value)
+    }
+
+    #[inline]
+    #[must_use] pub fn between(column: &str, low: u64, high: u64) -> Self {
+        Self::Between(column.to_owned(), low, high)
+    }
+}
+
+impl BitAnd for Predicate {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        Self::And(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl BitOr for Predicate {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Not for Predicate {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// The bitmaps built for one column.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct ColumnIndex {
+    encoding: ColumnEncoding,
+    /// Distinct values in increasing order.
+    values: Vec<u64>,
+    /// One bitmap per entry of `values`.
+    bitmaps: Vec<Bits>,
+}
+
+impl ColumnIndex {
+    fn new(column: &[u64], encoding: ColumnEncoding) -> Self {
+        let mut values = column.to_vec();
+        values.sort_unstable();
+        values.dedup();
+        let mut bitmaps = vec![Bits::new(&vec![Bit(false); column.len()]); values.len()];
+        for (row, value) in column.iter().enumerate() {
+            let position = values.binary_search(value).unwrap();
+            bitmaps[position].set(row, Bit(true));
+        }
+        if encoding == ColumnEncoding::Range {
+            for position in 1..bitmaps.len() {
+                let below = bitmaps[position - 1].clone();
+                bitmaps[position] |= below;
+            }
+        }
+        Self {
+            encoding,
+            values,
+            bitmaps,
+        }
+    }
+
+    /// Rows holding the value at `position` in `values`.
+    fn equal_at(&self, position: usize) -> Bits {
+        match self.encoding {
+            ColumnEncoding::Equality => self.bitmaps[position].clone(),
+            ColumnEncoding::Range if position == 0 => self.bitmaps[0].clone(),
+            ColumnEncoding::Range => {
+                self.bitmaps[position].clone() & !self.bitmaps[position - 1].clone()
+            }
+        }
+    }
+
+    /// Rows holding one of the `count` smallest values.
+    fn among_smallest(&self, count: usize, rows: usize) -> Bits {
+        let none = Bits::new(&vec![Bit(false); rows]);
+        if count == 0 {
+            return none
+        }
+        match self.encoding {
+            ColumnEncoding::Equality => self.bitmaps[..count]
+                .iter()
+                .fold(none, |matches, bitmap| matches | bitmap.clone()),
+            ColumnEncoding::Range => self.bitmaps[count - 1].clone(),
+        }
+    }
+
+    #[inline]
+    fn count_less_than(&self, value: u64) -> usize {
+        self.values.partition_point(|&candidate| candidate < value)
+    }
+
+    #[inline]
+    fn count_at_most(&self, value: u64) -> usize {
+        self.values.partition_point(|&candidate| candidate <= value)
+    }
+}
+
+/// Bitmaps over the rows of one or more columns of `u64` codes, answering
+/// `Predicate`s with the bitwise operators of `Bits`.
+///
+/// Every column must have the same number of rows.  Bit `row` of a result
+/// is set when row `row` matches.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct BitmapIndex {
+    rows: usize,
+    columns: BTreeMap<String, ColumnIndex>,
+}
+
+impl BitmapIndex {
+    #[inline]
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes `values` under `name`, replacing any column of that name.
+    pub fn add_column(&mut self, name: &str, values: &[u64], encoding: ColumnEncoding) {
+        let other_columns = self.columns.keys().any(|column| column != name);
+        assert!(
+            !other_columns || values.len() == self.rows,
+            "Column {name} has {} rows but the index has {}",
+            values.len(),
+            self.rows
+        );
+        self.rows = values.len();
+        self.columns.insert(name.to_owned(), ColumnIndex::new(values, encoding));
+    }
+
+    /// Chainable form of `add_column()`.
+    #[inline]
+    #[must_use] pub fn with_column(mut self, name: &str, values: &[u64], encoding: ColumnEncoding) -> Self {
+        self.add_column(name, values, encoding);
+        self
+    }
+
+    #[inline]
+    #[must_use] pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn column_names(&self) -> impl Iterator<Item = &str> {
+        self.columns.keys().map(String::as_str)
+    }
+
+    /// Distinct values of `column` in increasing order, or `None` when there
+    /// is no such column.
+    #[inline]
+    #[must_use] pub fn distinct_values(&self, column: &str) -> Option<&[u64]> {
+        self.columns.get(column).map(|column_index| column_index.values.as_slice())
+    }
+
+    /// A bitmap with one bit per row, set where `predicate` holds.
+    ///
+    /// # Errors
+    /// When `predicate` names a column the index does not have.
+    pub fn evaluate(&self, predicate: &Predicate) -> Result<Bits, UnknownColumn> {
+        let matches = match predicate {
+            Predicate::Equals(column, value) => self.equals(column, *value)?,
+            Predicate::In(column, values) => {
+                let mut matches = self.nothing();
+                for value in values {
+                    matches |= self.equals(column, *value)?;
+                }
+                matches
+            }
+            Predicate::LessThan(column, value) => {
+                let column_index = self.column(column)?;
+                column_index.among_smallest(column_index.count_less_than(*value), self.rows)
+            }
+            Predicate::LessOrEqual(column, value) => {
+                let column_index = self.column(column)?;
+                column_index.among_smallest(column_index.count_at_most(*value), self.rows)
+            }
+            Predicate::GreaterThan(column, value) => {
+                !self.evaluate(&Predicate::LessOrEqual(column.clone(), *value))?
+            }
+            Predicate::GreaterOrEqual(column, value) => {
+                !self.evaluate(&Predicate::LessThan(column.clone(), *value))?
+            }
+            Predicate::Between(column, low, high) => {
+                let column_index = self.column(column)?;
+                let at_most_high = column_index.count_at_most(*high);
+                let below_low = column_index.count_less_than(*low);
+                if below_low >= at_most_high {
+                    return Ok(self.nothing())
+                }
+                column_index.among_smallest(at_most_high, self.rows)
+                    & !column_index.among_smallest(below_low, self.rows)
+            }
+            Predicate::And(lhs, rhs) => self.evaluate(lhs)? & self.evaluate(rhs)?,
+            Predicate::Or(lhs, rhs) => self.evaluate(lhs)? | self.evaluate(rhs)?,
+            Predicate::Not(inner) => !self.evaluate(inner)?,
+        };
+        Ok(matches)
+    }
+
+    /// Ids of the rows where `predicate` holds, in increasing order.
+    ///
+    /// # Errors
+    /// When `predicate` names a column the index does not have.
+    #[inline]
+    pub fn matching_rows(&self, predicate: &Predicate) -> Result<Vec<usize>, UnknownColumn> {
+        Ok(self.evaluate(predicate)?.iter_ones().collect())
+    }
+
+    #[inline]
+    fn column(&self, name: &str) -> Result<&ColumnIndex, UnknownColumn> {
+        self.columns.get(name).ok_or_else(|| UnknownColumn {
+            name: name.to_owned(),
+        })
+    }
+
+    #[inline]
+    fn nothing(&self) -> Bits {
+        Bits::new(&vec![Bit(false); self.rows])
+    }
+
+    #[inline]
+    fn equals(&self, column: &str, value: u64) -> Result<Bits, UnknownColumn> {
+        let column_index = self.column(column)?;
+        Ok(match column_index.values.binary_search(&value) {
+            Ok(position) => column_index.equal_at(position),
+            Err(_) => self.nothing(),
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use crate as bitman;
+use bitman::{BitmapIndex, ColumnEncoding, Predicate, UnknownColumn};
+use proptest::prelude::*;
+
+fn sample_index(encoding: ColumnEncoding) -> BitmapIndex {
+    BitmapIndex::new()
+        .with_column("a", &[3, 1, 3, 2, 3, 7], encoding)
+        .with_column("b", &[1, 2, 5, 1, 2, 1], encoding)
+}
+
+#[test]
+fn conjunction_of_equality_and_membership() {
+    for encoding in [ColumnEncoding::Equality, ColumnEncoding::Range] {
+        let index = sample_index(encoding);
+        let predicate = Predicate::equals("a", 3) & Predicate::is_in("b", [1, 2]);
+        assert_eq!(index.matching_rows(&predicate), Ok(vec![0, 4]));
+        assert_eq!(index.evaluate(&predicate).unwrap().len(), 6);
+    }
+}
+
+#[test]
+fn comparisons_and_negation() {
+    for encoding in [ColumnEncoding::Equality, ColumnEncoding::Range] {
+        let index = sample_index(encoding);
+        assert_eq!(index.matching_rows(&Predicate::less_than("a", 3)), Ok(vec![1, 3]));
+        assert_eq!(index.matching_rows(&Predicate::less_or_equal("a", 3)), Ok(vec![0, 1, 2, 3, 4]));
+        assert_eq!(index.matching_rows(&Predicate::greater_than("a", 3)), Ok(vec![5]));
+        assert_eq!(index.matching_rows(&Predicate::greater_or_equal("a", 4)), Ok(vec![5]));
+        assert_eq!(index.matching_rows(&Predicate::between("a", 2, 3)), Ok(vec![0, 2, 3, 4]));
+        assert_eq!(index.matching_rows(&Predicate::between("a", 4, 6)), Ok(Vec::<usize>::new()));
+        assert_eq!(index.matching_rows(&!Predicate::equals("b", 1)), Ok(vec![1, 2, 4]));
+        assert_eq!(index.matching_rows(&(Predicate::equals("a", 7) | Predicate::equals("b", 5))), Ok(vec![2, 5]));
+        assert_eq!(index.matching_rows(&Predicate::equals("a", 100)), Ok(Vec::<usize>::new()));
+    }
+}
+
+#[test]
+fn describes_its_columns() {
+    let index = sample_index(ColumnEncoding::Equality);
+    assert_eq!(index.rows(), 6);
+    assert_eq!(index.column_names().collect::<Vec<&str>>(), vec!["a", "b"]);
+    assert_eq!(index.distinct_values("a"), Some(&[1, 2, 3, 7][..]));
+    assert_eq!(index.distinct_values("z"), None);
+}
+
+#[test]
+#[should_panic(expected = "rows")]
+fn rejects_columns_of_different_lengths() {
+    let _ = sample_index(ColumnEncoding::Equality).with_column("c", &[1, 2], ColumnEncoding::Equality);
+}
+
+#[test]
+fn unknown_columns_are_errors() {
+    let index = sample_index(ColumnEncoding::Range);
+    let unknown = Err(UnknownColumn { name: "z".to_owned() });
+    assert_eq!(index.evaluate(&Predicate::equals("z", 1)), unknown);
+    assert_eq!(index.evaluate(&(Predicate::equals("a", 3) & !Predicate::between("z", 1, 2))), unknown);
+    assert_eq!(index.matching_rows(&Predicate::is_in("z", [1])), Err(UnknownColumn { name: "z".to_owned() }));
+}
+
+proptest! {
+    #[test]
+    fn encodings_agree_with_a_row_scan(
+        rows in proptest::collection::vec((0u64..8, 0u64..8), 0..60),
+        low in 0u64..9,
+        high in 0u64..9,
+        members in proptest::collection::vec(0u64..9, 0..4),
+    ) {
+        let a: Vec<u64> = rows.iter().map(|&(a, _)| a).collect();
+        let b: Vec<u64> = rows.iter().map(|&(_, b)| b).collect();
+        let predicate = (Predicate::between("a", low, high) & !Predicate::is_in("b", members.clone()))
+            | Predicate::greater_than("b", high);
+        let expected: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, (a, b))| (low <= *a && *a <= high && !members.contains(b)) || *b > high)
+            .map(|(row, _)| row)
+            .collect();
+        for encoding in [ColumnEncoding::Equality, ColumnEncoding::Range] {
+            let index = BitmapIndex::new().with_column("a", &a, encoding).with_column("b", &b, encoding);
+            prop_assert_eq!(index.matching_rows(&predicate), Ok(expected.clone()));
+        }
+    }
+}
@@ -1,17 +1,18 @@
 use core::{
     fmt::{self, Debug, Display},
-    ops::{Deref, DerefMut},
-    slice::SliceIndex,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    ops::{Bound, RangeBounds, RangeFull},
 };
 use core::{
     mem::size_of,
     ops::{
-        Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, IndexMut, Mul,
-        Not, Shl, Shr,
+        Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Mul, Not, Shl,
+        Shr,
     },
 };
 extern crate alloc;
-use alloc::borrow::ToOwned;
+use alloc::borrow::Cow;
 use alloc::format;
 use alloc::string::String;
 use alloc::vec;
@@ -19,41 +20,51 @@ use alloc::vec::Vec;
 use num_traits::{CheckedShl, One, Zero};
 
 use crate::bit::Bit;
-use crate::BitMan;
+use crate::bit_storage::{word_len, BitStorage};
+use crate::kernels::{self, BinaryOp};
+use crate::scanning::{read_field, write_field};
+use crate::{BitMan, BitPositions, BitRuns};
 
 #[cfg(test)]
 mod bits_tests;
 
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+/// Length of `Bits::zero()` and `Bits::one()`, the bit width `Bits` had when
+/// it wrapped a `Vec`, kept so these values do not follow the storage layout.
+const ZERO_LEN: usize = 192;
+
+static ZERO: Bit = Bit(false);
+static ONE: Bit = Bit(true);
+
+/// A sequence of bits, the first one the most significant when converting
+/// to and from integers.
+///
+/// The bits are packed into 64 bit words.  Up to 128 of them are kept in
+/// place and longer ones on the heap, so a `Bits` is the size of a `Vec`.
+#[derive(Debug, Default, Clone)]
 pub struct Bits {
-    inner: Vec<Bit>,
+    inner: BitStorage,
 }
 
 impl Bits {
     #[inline]
     #[must_use] pub fn new(inner_vector_of_bits: &[Bit]) -> Self {
         return Self {
-            inner: inner_vector_of_bits.to_owned(),
+            inner: BitStorage::from(inner_vector_of_bits),
         }
     }
 
+    /// Packs the bits eight to a byte, most significant first.  When the
+    /// length is not a multiple of eight the first byte is padded with
+    /// leading zeros, as when converting to an integer.
     #[inline]
     #[must_use] pub fn to_be_bytes(&self) -> Vec<u8> {
-        let mut bytes: Vec<u8> = Vec::new();
-        let length = self.len();
-        let mut current_length = 0usize;
-        loop {
-            let mut bits: Vec<Bit> = Vec::new();
-            for (count, bit) in self.inner.iter().enumerate() {
-                bits.push(*bit);
-                if count % 8 == 0 {
-                    bytes.push(u8::from(&Self::new(&bits)));
-                    current_length += 8;
-                }
-            }
-            if current_length >= length {
-                break;
-            }
+        let head_len = self.len() % 8;
+        let mut bytes = vec![0u8; (self.len() + 7) / 8];
+        if head_len == 0 {
+            self.pack_be(&mut bytes);
+        } else {
+            bytes[0] = self.field(0, head_len as u32) as u8;
+            self.slice(head_len..).pack_be(&mut bytes[1..]);
         }
         bytes
     }
@@ -72,32 +83,16 @@ impl Bits {
 
     #[inline]
     #[must_use] pub fn to_be_bytes_of_le_bits(&self) -> Vec<u8> {
-        let mut bytes: Vec<u8> = Vec::new();
-        let length = self.len();
-        let mut current_length = 0usize;
-        loop {
-            let mut bits: Vec<Bit> = Vec::new();
-            for (count, bit) in self.inner.iter().enumerate() {
-                bits.push(*bit);
-                if count >= 7 {
-                    bits.reverse();
-                    break;
-                }
-            }
-            bytes.push(u8::from(&Self::new(&bits)));
-            current_length += 8;
-            if current_length >= length {
-                break;
-            }
-        }
-        bytes
+        self.to_be_bytes().into_iter().map(u8::reverse_bits).collect()
     }
 
     #[inline]
     #[must_use] pub fn from_be_bytes(slice_of_bytes: &[u8]) -> Self {
-        let mut bits = Self::new(&[]);
-        for current_u8 in slice_of_bytes {
-            bits.append(&mut current_u8.bits().inner);
+        let mut bits = Self::zeroed(slice_of_bytes.len() * 8);
+        let whole_words = slice_of_bytes.len() / 8;
+        kernels::unpack_be(&slice_of_bytes[..whole_words * 8], &mut bits.words_mut()[..whole_words]);
+        for (index, current_u8) in slice_of_bytes.iter().enumerate().skip(whole_words * 8) {
+            bits.set_field(index * 8, 8, u64::from(*current_u8));
         }
         bits
     }
@@ -118,28 +113,275 @@ impl Bits {
 
     #[inline]
     pub fn from_be_bytes_of_le_bits(slice_of_bytes: &mut [u8]) -> Self {
-        let mut vec_of_bits: Vec<Bit> = Vec::new();
-        for current_u8 in slice_of_bytes {
-            let mut current_u8_as_bits: Self = Self::new(&Self::from(*current_u8).inner);
-            vec_of_bits.append(&mut current_u8_as_bits);
+        let vec_of_bytes: Vec<u8> = slice_of_bytes.iter().map(|current_u8| current_u8.reverse_bits()).collect();
+        return Self::from_be_bytes(&vec_of_bytes)
+    }
+
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    #[must_use] pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len() {
+            return None
+        }
+        Some(Bit((self.words()[index / 64] >> (index % 64)) & 1 == 1))
+    }
+
+    /// Overwrites the bit at `index`.
+    ///
+    /// # Panics
+    /// When `index` is out of bounds.
+    #[inline]
+    pub fn set(&mut self, index: usize, bit: Bit) {
+        assert!(
+            index < self.len(),
+            "Index {index} passed to set() is beyond the bounds of Bits of length {}",
+            self.len()
+        );
+        let word = &mut self.words_mut()[index / 64];
+        *word = (*word & !(1 << (index % 64))) | (u64::from(bit.0) << (index % 64));
+    }
+
+    #[inline]
+    pub fn push(&mut self, bit: Bit) {
+        let len = self.len();
+        self.inner.set_len(len + 1);
+        self.set(len, bit);
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<Bit> {
+        let last = self.len().checked_sub(1)?;
+        let bit = self.get(last);
+        self.inner.set_len(last);
+        bit
+    }
+
+    /// Shortens to `new_len` bits, doing nothing when already shorter.
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len() {
+            self.inner.set_len(new_len);
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.inner.set_len(0);
+    }
+
+    /// Changes the length to `new_len`, filling any new bits with `bit`.
+    pub fn resize(&mut self, new_len: usize, bit: Bit) {
+        let len = self.len();
+        self.inner.set_len(new_len);
+        if bit.0 && new_len > len {
+            for offset in (len..new_len).step_by(64) {
+                self.set_field(offset, (new_len - offset).min(64) as u32, u64::MAX);
+            }
+        }
+    }
+
+    /// Makes room for `additional` more bits.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
+    #[inline]
+    pub fn extend_from_slice(&mut self, other: &[Bit]) {
+        self.append(&mut Self::new(other));
+    }
+
+    /// Moves every bit of `other` onto the end, leaving it empty.
+    pub fn append(&mut self, other: &mut Self) {
+        let start = self.len();
+        self.inner.set_len(start + other.len());
+        for (word_index, word) in other.words().iter().enumerate() {
+            let offset = word_index * 64;
+            write_field(self.inner.words_mut(), start + offset, (other.len() - offset).min(64) as u32, *word);
+        }
+        other.clear();
+    }
+
+    /// A copy of the bits in `range`.
+    ///
+    /// # Panics
+    /// When `range` is out of order or reaches past the end.
+    #[must_use] pub fn slice<Range: RangeBounds<usize>>(&self, range: Range) -> Self {
+        let (start, end) = self.range_to_bounds(&range);
+        let mut slice = Self::zeroed(end - start);
+        for (word_index, word) in slice.words_mut().iter_mut().enumerate() {
+            let offset = start + word_index * 64;
+            *word = read_field(self.words(), offset, (end - offset).min(64) as u32);
+        }
+        slice
+    }
+
+    #[inline]
+    #[must_use] pub fn iter(&self) -> BitsIter<'_> {
+        BitsIter {
+            bits: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn to_vec(&self) -> Vec<Bit> {
+        self.iter().collect()
+    }
+
+    /// `len` zero bits.
+    #[inline]
+    pub(crate) fn zeroed(len: usize) -> Self {
+        Self {
+            inner: BitStorage::zeroed(len),
+        }
+    }
+
+    /// `range` as `start..end`, panicking like slicing when it is out of
+    /// order or reaches past the end.
+    pub(crate) fn range_to_bounds<Range: RangeBounds<usize>>(&self, range: &Range) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => Some(self.len()),
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end && end <= self.len() => (start, end),
+            (Some(start), Some(end)) => panic!(
+                "Range {start}..{end} is beyond the bounds of Bits of length {}",
+                self.len()
+            ),
+            _ => panic!(
+                "Range reaching past usize::MAX is beyond the bounds of Bits of length {}",
+                self.len()
+            ),
+        }
+    }
+
+    /// The bits packed into words, bit `i` at bit `i % 64` of word `i / 64`.
+    /// Bits past the end of the last word are zero.
+    #[inline]
+    pub(crate) fn words(&self) -> &[u64] {
+        self.inner.words()
+    }
+
+    /// Mutable `words()`.  Bits past the end of the last word must be left
+    /// zero.
+    #[inline]
+    pub(crate) fn words_mut(&mut self) -> &mut [u64] {
+        self.inner.words_mut()
+    }
+
+    /// Zeroes the bits of the last word past the end, after writing whole
+    /// words.
+    #[inline]
+    pub(crate) fn clear_padding(&mut self) {
+        self.inner.clear_padding();
+    }
+
+    /// Whether the bits are held in place rather than on the heap.
+    #[cfg(test)]
+    pub(crate) fn is_inline(&self) -> bool {
+        self.inner.is_inline()
+    }
+
+    /// The `width` bits from `offset` as an integer, the first most
+    /// significant.  `width` is 1 to 64.
+    #[inline]
+    pub(crate) fn field(&self, offset: usize, width: u32) -> u64 {
+        read_field(self.words(), offset, width).reverse_bits() >> (64 - width)
+    }
+
+    /// Overwrites the `width` bits from `offset` with the low `width` bits
+    /// of `value`, the most significant first.  `width` is 1 to 64.
+    #[inline]
+    pub(crate) fn set_field(&mut self, offset: usize, width: u32, value: u64) {
+        write_field(self.words_mut(), offset, width, value.reverse_bits() >> (64 - width));
+    }
+
+    /// Packs the bits into `out`, eight to a byte with the first most
+    /// significant.  The length must be `out.len() * 8`.
+    pub(crate) fn pack_be(&self, out: &mut [u8]) {
+        let whole_words = self.len() / 64;
+        kernels::pack_be(&self.words()[..whole_words], &mut out[..whole_words * 8]);
+        for (index, byte) in out.iter_mut().enumerate().skip(whole_words * 8) {
+            *byte = self.field(index * 8, 8) as u8;
+        }
+    }
+
+    /// `op` applied to the bits both sides cover.
+    #[inline]
+    fn combine(&self, rhs: &Self, op: BinaryOp) -> Self {
+        let len = self.len().min(rhs.len());
+        let words = word_len(len);
+        let mut combined = Self::zeroed(len);
+        kernels::binary(op, &self.words()[..words], &rhs.words()[..words], combined.words_mut());
+        combined.clear_padding();
+        combined
+    }
+
+    /// `op` applied in place to the bits both sides cover, leaving any
+    /// further bits of `self` untouched.
+    #[inline]
+    fn combine_assign(&mut self, rhs: &Self, op: BinaryOp) {
+        let len = self.len().min(rhs.len());
+        let whole_words = len / 64;
+        kernels::binary_assign(op, &mut self.words_mut()[..whole_words], &rhs.words()[..whole_words]);
+        if len % 64 != 0 {
+            let mask = u64::MAX >> (64 - len % 64);
+            let (lhs_word, rhs_word) = (self.words()[whole_words], rhs.words()[whole_words] & mask);
+            self.words_mut()[whole_words] = match op {
+                BinaryOp::And => lhs_word & (rhs_word | !mask),
+                BinaryOp::Or => lhs_word | rhs_word,
+                BinaryOp::Xor => lhs_word ^ rhs_word,
+            };
         }
-        return Self::new(&Vec::new())
     }
 }
 
-impl Deref for Bits {
-    type Target = Vec<Bit>;
+impl PartialEq for Bits {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && kernels::equal(self.words(), other.words())
+    }
+}
+
+impl Eq for Bits {}
+
+impl PartialEq<[Bit]> for Bits {
+    #[inline]
+    fn eq(&self, other: &[Bit]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
 
+impl<const N: usize> PartialEq<[Bit; N]> for Bits {
     #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+    fn eq(&self, other: &[Bit; N]) -> bool {
+        *self == other[..]
     }
 }
 
-impl DerefMut for Bits {
+impl Hash for Bits {
     #[inline]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        self.words().hash(state);
     }
 }
 
@@ -161,33 +403,14 @@ impl BitAnd for Bits {
 
     #[inline]
     fn bitand(self, rhs: Self) -> Self {
-        let mut new_bits = Self { inner: Vec::new() };
-        let mut index_counter = 0usize;
-        while let Some(bit_from_self) = self.get(index_counter) {
-            if let Some(bit_from_rhs) = rhs.get(index_counter) {
-                new_bits.push(*bit_from_self & *bit_from_rhs);
-                index_counter += 1;
-            } else {
-                break;
-            }
-        }
-        new_bits
+        self.combine(&rhs, BinaryOp::And)
     }
 }
 
 impl BitAndAssign for Bits {
     #[inline]
     fn bitand_assign(&mut self, rhs: Self) {
-        let mut index_counter = 0usize;
-        let old_self = self.clone();
-        while let Some(bit_from_self) = old_self.get(index_counter) {
-            if let Some(bit_from_rhs) = rhs.get(index_counter) {
-                self.set_bit(&(index_counter as u32), &(*bit_from_self & *bit_from_rhs));
-                index_counter += 1;
-            } else {
-                break;
-            }
-        }
+        self.combine_assign(&rhs, BinaryOp::And);
     }
 }
 
@@ -196,28 +419,14 @@ impl BitOr for Bits {
 
     #[inline]
     fn bitor(self, rhs: Self) -> Self {
-        let mut new_bits = Self { inner: Vec::new() };
-        let mut index_counter = 0usize;
-        while let Some(bit_from_self) = self.get(index_counter) {
-            if let Some(bit_from_rhs) = rhs.get(index_counter) {
-                new_bits.push(*bit_from_self | *bit_from_rhs);
-                index_counter += 1;
-            } else {
-                break;
-            }
-        }
-        new_bits
+        self.combine(&rhs, BinaryOp::Or)
     }
 }
 
 impl BitOrAssign for Bits {
     #[inline]
     fn bitor_assign(&mut self, rhs: Self) {
-        for index in 0..self.len() {
-            if let Some(rhs_bit) = rhs.get(index) {
-                self.inner[index] |= *rhs_bit;
-            }
-        }
+        self.combine_assign(&rhs, BinaryOp::Or);
     }
 }
 
@@ -226,50 +435,36 @@ impl BitXor for Bits {
 
     #[inline]
     fn bitxor(self, rhs: Self) -> Self {
-        let mut new_bits = Self { inner: Vec::new() };
-        let mut index_counter = 0usize;
-        while let Some(bit_from_self) = self.get(index_counter) {
-            if let Some(bit_from_rhs) = rhs.get(index_counter) {
-                new_bits.push(*bit_from_self ^ *bit_from_rhs);
-                index_counter += 1;
-            } else {
-                break;
-            }
-        }
-        new_bits
+        self.combine(&rhs, BinaryOp::Xor)
     }
 }
 
 impl BitXorAssign for Bits {
     #[inline]
     fn bitxor_assign(&mut self, rhs: Self) {
-        for index in 0..self.len() {
-            if let Some(rhs_bit) = rhs.get(index) {
-                self[index] ^= *rhs_bit;
-            }
-        }
+        self.combine_assign(&rhs, BinaryOp::Xor);
     }
 }
 
-impl<Idx> Index<Idx> for Bits
-where
-    Idx: SliceIndex<[Bit]>,
-{
-    type Output = Idx::Output;
+impl Index<usize> for Bits {
+    type Output = Bit;
 
     #[inline]
-    fn index(&self, index: Idx) -> &Self::Output {
-        return self.get(index).unwrap()
+    fn index(&self, index: usize) -> &Bit {
+        match self.get(index) {
+            Some(Bit(true)) => &ONE,
+            Some(Bit(false)) => &ZERO,
+            None => panic!("Index {index} is beyond the bounds of Bits of length {}", self.len()),
+        }
     }
 }
 
-impl<Idx> IndexMut<Idx> for Bits
-where
-    Idx: SliceIndex<[Bit]>,
-{
+impl Index<RangeFull> for Bits {
+    type Output = Self;
+
     #[inline]
-    fn index_mut(&mut self, index: Idx) -> &mut Self::Output {
-        return self.get_mut(index).unwrap()
+    fn index(&self, _index: RangeFull) -> &Self {
+        self
     }
 }
 
@@ -277,16 +472,10 @@ impl Not for Bits {
     type Output = Self;
 
     #[inline]
-    fn not(self) -> Self::Output {
-        let mut new_bits = Self { inner: Vec::new() };
-        for index in 0..self.len() {
-            if self.get(index).unwrap().0 {
-                new_bits.push(Bit(true));
-            } else {
-                new_bits.push(Bit(false));
-            }
-        }
-        new_bits
+    fn not(mut self) -> Self::Output {
+        kernels::not_assign(self.words_mut());
+        self.clear_padding();
+        self
     }
 }
 
@@ -302,12 +491,10 @@ impl Shl<usize> for Bits {
     type Output = Self;
 
     #[inline]
-    fn shl(mut self, rhs: usize) -> Self {
-        drop(self.drain(..rhs));
-        for _ in 0..rhs {
-            self.push(Bit(false));
-        }
-        self
+    fn shl(self, rhs: usize) -> Self {
+        let mut shifted = self.slice(rhs..);
+        shifted.resize(self.len(), Bit(false));
+        shifted
     }
 }
 
@@ -315,12 +502,8 @@ impl Shl<u32> for Bits {
     type Output = Self;
 
     #[inline]
-    fn shl(mut self, rhs: u32) -> Self {
-        drop(self.drain(..rhs as usize));
-        for _ in 0..rhs {
-            self.push(Bit(false));
-        }
-        self
+    fn shl(self, rhs: u32) -> Self {
+        self << rhs as usize
     }
 }
 
@@ -328,12 +511,10 @@ impl Shr<usize> for Bits {
     type Output = Self;
 
     #[inline]
-    fn shr(mut self, rhs: usize) -> Self::Output {
-        drop(self.inner.drain(..rhs));
-        for _ in 0..rhs {
-            self.inner.push(Bit(false));
-        }
-        self
+    fn shr(self, rhs: usize) -> Self::Output {
+        let mut shifted = self.slice(rhs..);
+        shifted.resize(self.len(), Bit(false));
+        shifted
     }
 }
 
@@ -341,12 +522,8 @@ impl Shr<u32> for Bits {
     type Output = Self;
 
     #[inline]
-    fn shr(mut self, rhs: u32) -> Self::Output {
-        drop(self.inner.drain(..rhs as usize));
-        for _ in 0..rhs {
-            self.inner.push(Bit(false));
-        }
-        self
+    fn shr(self, rhs: u32) -> Self::Output {
+        self >> rhs as usize
     }
 }
 
@@ -364,20 +541,20 @@ impl CheckedShl for Bits {
 impl Zero for Bits {
     #[inline]
     fn zero() -> Self {
-        return Self::new(&vec![Bit(false); size_of::<Self>() * 8])
+        return Self::new(&[Bit(false); ZERO_LEN])
     }
 
     #[inline]
     fn is_zero(&self) -> bool {
-        self.inner.iter().all(|&x| !x.0)
+        self.words().iter().all(|&word| word == 0)
     }
 }
 
 impl One for Bits {
     #[inline]
     fn one() -> Self {
-        let mut output = Self::new(&vec![Bit(false); size_of::<Self>() * 8]);
-        output.set_bit(&((size_of::<Self>()) as u32 * 7), &Bit(true));
+        let mut output = Self::new(&[Bit(false); ZERO_LEN]);
+        output.set_bit(&(ZERO_LEN as u32 / 8 * 7), &Bit(true));
         output
     }
 
@@ -387,12 +564,11 @@ impl One for Bits {
         Self: PartialEq,
     {
         (self
-            .get(0..self.inner.len() - 1)
-            .unwrap()
+            .slice(0..self.len() - 1)
             .iter()
-            .all(|&x| !x.0)
-            || self.inner.len() == 1)
-            && self.get(self.inner.len()).unwrap().0
+            .all(|x| !x.0)
+            || self.len() == 1)
+            && self.get(self.len()).unwrap().0
     }
 }
 
@@ -409,7 +585,7 @@ impl Mul for Bits {
 impl BitMan for Bits {
     #[inline]
     fn bit_len(&self) -> usize {
-        (*self).len()
+        self.len()
     }
 
     #[inline]
@@ -419,7 +595,7 @@ impl BitMan for Bits {
 
     #[inline]
     default fn set_bit(&mut self, index: &u32, bit: &Bit) {
-        self[*index as usize] = *bit;
+        self.set(*index as usize, *bit);
     }
 
     #[inline]
@@ -427,22 +603,123 @@ impl BitMan for Bits {
         self.clone()
     }
 
+    /// Copies `bits` in a word at a time.
     #[inline]
-    default fn set_bits(&mut self, mut index: u32, bits: &Bits) {
-        for bit in bits.iter() {
-            self[index as usize] = *bit;
-            index += 1;
+    default fn set_bits(&mut self, index: u32, bits: &Bits) {
+        let start = index as usize;
+        assert!(
+            start + bits.len() <= self.len(),
+            "Writing bits {start}..{} is beyond the bounds of Bits of length {}",
+            start + bits.len(),
+            self.len()
+        );
+        for (word_index, word) in bits.words().iter().enumerate() {
+            let offset = word_index * 64;
+            write_field(self.words_mut(), start + offset, (bits.len() - offset).min(64) as u32, *word);
         }
     }
+
+    #[inline]
+    fn iter_ones(&self) -> BitPositions<'_> {
+        BitPositions::new(Cow::Borrowed(self), Bit(true))
+    }
+
+    #[inline]
+    fn iter_zeros(&self) -> BitPositions<'_> {
+        BitPositions::new(Cow::Borrowed(self), Bit(false))
+    }
+
+    #[inline]
+    fn iter_runs(&self) -> BitRuns<'_> {
+        BitRuns::new(Cow::Borrowed(self))
+    }
 }
 
 impl Iterator for Bits {
     type Item = Bit;
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.pop()
+        self.pop()
+    }
+}
+
+impl<'a> IntoIterator for &'a Bits {
+    type Item = Bit;
+    type IntoIter = BitsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Bit> for Bits {
+    #[inline]
+    fn from_iter<Iter: IntoIterator<Item = Bit>>(bits: Iter) -> Self {
+        let mut collected = Self::new(&[]);
+        collected.extend(bits);
+        collected
+    }
+}
+
+impl Extend<Bit> for Bits {
+    #[inline]
+    fn extend<Iter: IntoIterator<Item = Bit>>(&mut self, bits: Iter) {
+        let bits = bits.into_iter();
+        self.reserve(bits.size_hint().0);
+        for bit in bits {
+            self.push(bit);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Bit> for Bits {
+    #[inline]
+    fn extend<Iter: IntoIterator<Item = &'a Bit>>(&mut self, bits: Iter) {
+        self.extend(bits.into_iter().copied());
+    }
+}
+
+/// Iterator over the bits of a `Bits` in order.
+#[derive(Debug, Clone)]
+pub struct BitsIter<'a> {
+    bits: &'a Bits,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for BitsIter<'_> {
+    type Item = Bit;
+
+    #[inline]
+    fn next(&mut self) -> Option<Bit> {
+        if self.front == self.back {
+            return None
+        }
+        self.front += 1;
+        self.bits.get(self.front - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
     }
 }
 
+impl DoubleEndedIterator for BitsIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Bit> {
+        if self.front == self.back {
+            return None
+        }
+        self.back -= 1;
+        self.bits.get(self.back)
+    }
+}
+
+impl ExactSizeIterator for BitsIter<'_> {}
+
+impl FusedIterator for BitsIter<'_> {}
+
 #[macro_export]
 macro_rules! impl_to_and_from_bits {
     ($($new_type:ty$(,)?)*) => {$(
@@ -450,18 +727,16 @@ macro_rules! impl_to_and_from_bits {
             #[inline]
             fn from(bits_to_convert: &Bits) -> $new_type {
                 #[allow(clippy::manual_bits)] if bits_to_convert.bit_len() > size_of::<$new_type>() * 8 {
-                    let shortened_bits: Bits = Bits{
-                        inner: bits_to_convert.get((bits_to_convert.inner.len() - size_of::<$new_type>())..bits_to_convert.inner.len()).unwrap()
-                            .to_vec()
-                    };
+                    let shortened_bits: Bits =
+                        bits_to_convert.slice((bits_to_convert.len() - size_of::<$new_type>())..bits_to_convert.len());
                     <$new_type>::from(&shortened_bits)
                 } else {
                     let mut new_value: $new_type = Default::default();
                     for (index, current_bit) in bits_to_convert.iter().enumerate() {
                         new_value.set_bit(&(index as u32), &current_bit);
                     }
-                    if bits_to_convert.inner.len() < size_of::<$new_type>() {
-                        new_value >>= size_of::<$new_type>() - bits_to_convert.inner.len();
+                    if bits_to_convert.len() < size_of::<$new_type>() {
+                        new_value >>= size_of::<$new_type>() - bits_to_convert.len();
                     }
                     new_value
                 }
@@ -472,7 +747,7 @@ macro_rules! impl_to_and_from_bits {
             fn from<'a>(value_to_convert: $new_type) -> Bits {
                 let mut output_value: Bits = Default::default();
                 for index in 0..size_of::<$new_type>() {
-                    output_value.inner.push(value_to_convert.bit(&(index as u32)));
+                    output_value.push(value_to_convert.bit(&(index as u32)));
                 }
                 output_value
             }
@@ -489,24 +764,23 @@ impl Add for Bits {
     fn add(self, rhs: Self) -> Self::Output {
         let mut output_value: Self::Output = Default::default();
         let mut carry = false;
-        for index in self.inner.len()..0 {
+        for index in self.len()..0 {
             if !self.get(index).unwrap().0 {
                 if carry {
                     if !rhs.get(index).unwrap().0 {
                         carry = false;
                     }
-                    output_value.inner.push(Bit(true));
+                    output_value.push(Bit(true));
                 } else {
-                    output_value.inner.push(*rhs.get(index).unwrap());
+                    output_value.push(rhs.get(index).unwrap());
                 }
             } else {
                 if rhs.get(index).unwrap().0 {
                     carry = true;
                 }
-                output_value.inner.push(!*rhs.get(index).unwrap());
+                output_value.push(!rhs.get(index).unwrap());
             }
         }
-        output_value.inner.reverse();
-        output_value
+        output_value.iter().rev().collect()
     }
 }
@@ -80,4 +80,35 @@ use crate as bitman;
         assert_eq!(new_bits[0], bitman::Bit(false));
         assert_eq!(0u8.bits(), bitman::Bits::new(&[bitman::Bit::zero(); 8]));
     }
-    
\ No newline at end of file
+    
+
+    #[test]
+    fn not_inverts_every_bit() {
+        let bits = bitman::Bits::from_be_bytes(&[0b1010_0011, 0b0000_1111]);
+        assert_eq!(!bits, bitman::Bits::from_be_bytes(&[0b0101_1100, 0b1111_0000]));
+        assert_eq!(!bitman::Bits::new(&[]), bitman::Bits::new(&[]));
+    }
+
+    #[test]
+    fn byte_conversions_round_trip() {
+        let bytes = [0b1000_0001, 0b0011_0100, 0b1111_0000];
+        let bits = bitman::Bits::from_be_bytes(&bytes);
+        assert_eq!(bits.to_be_bytes(), bytes);
+        assert_eq!(bitman::Bits::from_le_bytes(&bytes).to_le_bytes(), bytes);
+        assert_eq!(bitman::Bits::from_le_bytes_of_le_bits(&bytes).to_le_bytes_of_le_bits(), bytes);
+        assert_eq!(bitman::Bits::from_be_bytes_of_le_bits(&mut bytes.clone()).to_be_bytes_of_le_bits(), bytes);
+        assert_eq!(bitman::Bits::from_be_bytes_of_le_bits(&mut [0b0000_0001]), bitman::Bits::from_be_bytes(&[0b1000_0000]));
+        assert_eq!(bits.slice(4..).to_be_bytes(), [0b0000_0001, 0b0011_0100, 0b1111_0000]);
+        assert_eq!(bitman::Bits::new(&[]).to_be_bytes(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn zero_and_one_keep_their_length() {
+        let zero = bitman::Bits::zero();
+        assert_eq!(zero.len(), 192);
+        assert!(zero.is_zero());
+        let one = bitman::Bits::one();
+        assert_eq!(one.len(), 192);
+        assert_eq!(one.count_ones(), 1);
+        assert_eq!(one.first_one(), Some(168));
+    }
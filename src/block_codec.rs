@@ -0,0 +1,249 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+use crate::{Bit, BitMan, Bits, IntVec};
+
+#[cfg(test)]
+mod block_codec_tests;
+
+/// Returned when bits or bytes passed to `BlockCodec::decode()` or
+/// `EncodedBlocks::from_bytes()` were not produced by `BlockCodec::encode()`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct InvalidBlockEncoding {
+    pub reason: &'static str,
+}
+
+impl Display for InvalidBlockEncoding {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "Invalid block encoding: {}", self.reason)
+    }
+}
+
+/// How each block of values is turned into small non-negative integers
+/// before it is bit packed.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum BlockTransform {
+    /// Values are packed as they are.
+    Plain,
+    /// The smallest value of the block is subtracted from every value.
+    FrameOfReference,
+    /// Differences between neighbouring values are packed.  Values must be
+    /// non-decreasing.
+    Delta,
+    /// Differences between neighbouring values are zigzag encoded, so any
+    /// sequence can be packed.
+    ZigZagDelta,
+}
+
+/// Bit packs `u64` values in blocks of `BLOCK_LEN`, each block with the
+/// smallest width that holds its transformed values.
+///
+/// The encoded bits start with the number of values as 64 bits.  Every
+/// block then holds its width in 8 bits, its reference value in 64 bits and
+/// its transformed values packed with that width.  The last block may be
+/// shorter than `BLOCK_LEN`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct BlockCodec {
+    transform: BlockTransform,
+}
+
+impl BlockCodec {
+    pub const BLOCK_LEN: usize = 128;
+
+    #[inline]
+    #[must_use] pub fn new(transform: BlockTransform) -> Self {
+        Self { transform }
+    }
+
+    #[inline]
+    #[must_use] pub fn transform(&self) -> BlockTransform {
+        self.transform
+    }
+
+    #[must_use] pub fn encode(&self, values: &[u64]) -> EncodedBlocks {
+        let mut encoded = (values.len() as u64).bits();
+        for block in values.chunks(Self::BLOCK_LEN) {
+            let (reference, transformed) = self.forward(block);
+            let largest = transformed.iter().copied().max().unwrap_or(0);
+            let width = 64 - largest.leading_zeros();
+            encoded.extend((width as u8).bits().iter());
+            encoded.extend(reference.bits().iter());
+            if width > 0 {
+                encoded.extend(IntVec::from_values(&transformed, width).to_bits().iter());
+            }
+        }
+        EncodedBlocks { bits: encoded }
+    }
+
+    pub fn decode(&self, encoded: &EncodedBlocks) -> Result<Vec<u64>, InvalidBlockEncoding> {
+        let encoded = &encoded.bits;
+        let mut position = 0;
+        let len = read_value(encoded, &mut position, 64)? as usize;
+        let mut values = Vec::new();
+        while values.len() < len {
+            let block_len = (len - values.len()).min(Self::BLOCK_LEN);
+            let width = read_value(encoded, &mut position, 8)? as u32;
+            let reference = read_value(encoded, &mut position, 64)?;
+            if width > 64 {
+                return Err(InvalidBlockEncoding {
+                    reason: "block width above 64 bits",
+                })
+            }
+            let mut transformed = Vec::with_capacity(block_len);
+            for _ in 0..block_len {
+                transformed.push(read_value(encoded, &mut position, width as usize)?);
+            }
+            self.inverse(reference, &transformed, &mut values)?;
+        }
+        if position != encoded.len() {
+            return Err(InvalidBlockEncoding {
+                reason: "bits left over after the last block",
+            })
+        }
+        Ok(values)
+    }
+
+    fn forward(&self, block: &[u64]) -> (u64, Vec<u64>) {
+        match self.transform {
+            BlockTransform::Plain => (0, block.to_vec()),
+            BlockTransform::FrameOfReference => {
+                let reference = block.iter().copied().min().unwrap_or(0);
+                (
+                    reference,
+                    block.iter().map(|&value| value - reference).collect(),
+                )
+            }
+            BlockTransform::Delta | BlockTransform::ZigZagDelta => {
+                let reference = block[0];
+                let mut previous = reference;
+                let transformed = block
+                    .iter()
+                    .map(|&value| {
+                        let difference = if self.transform == BlockTransform::Delta {
+                            value.checked_sub(previous).unwrap_or_else(|| {
+                                panic!("Delta packing needs non-decreasing values, got {} after {}", value, previous)
+                            })
+                        } else {
+                            let difference = value.wrapping_sub(previous) as i64;
+                            ((difference << 1) ^ (difference >> 63)) as u64
+                        };
+                        previous = value;
+                        difference
+                    })
+                    .collect();
+                (reference, transformed)
+            }
+        }
+    }
+
+    fn inverse(
+        &self,
+        reference: u64,
+        transformed: &[u64],
+        values: &mut Vec<u64>,
+    ) -> Result<(), InvalidBlockEncoding> {
+        let overflow = InvalidBlockEncoding {
+            reason: "decoded value does not fit in 64 bits",
+        };
+        let mut previous = reference;
+        for &value in transformed {
+            let decoded = match self.transform {
+                BlockTransform::Plain => value,
+                BlockTransform::FrameOfReference => reference.checked_add(value).ok_or(overflow)?,
+                BlockTransform::Delta => previous.checked_add(value).ok_or(overflow)?,
+                BlockTransform::ZigZagDelta => {
+                    previous.wrapping_add(((value >> 1) as i64 ^ -((value & 1) as i64)) as u64)
+                }
+            };
+            previous = decoded;
+            values.push(decoded);
+        }
+        Ok(())
+    }
+}
+
+/// The bits produced by `BlockCodec::encode()`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct EncodedBlocks {
+    bits: Bits,
+}
+
+impl EncodedBlocks {
+    /// Number of encoded bits.
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    /// The number of encoded bits as 8 big endian bytes, followed by the
+    /// bits eight to a byte, most significant first.  The last byte is
+    /// padded with zeros.
+    #[must_use] pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + (self.bits.len() + 7) / 8);
+        bytes.extend_from_slice(&(self.bits.len() as u64).to_be_bytes());
+        let mut padded = self.bits.clone();
+        padded.resize((self.bits.len() + 7) / 8 * 8, Bit(false));
+        bytes.resize(8 + padded.len() / 8, 0);
+        padded.pack_be(&mut bytes[8..]);
+        bytes
+    }
+
+    /// Reads bytes written by `to_bytes()`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InvalidBlockEncoding> {
+        let truncated = InvalidBlockEncoding {
+            reason: "bytes end before the recorded length",
+        };
+        let (len_bytes, body) = (bytes.get(..8).ok_or(truncated)?, &bytes[8..]);
+        let len = u64::from_be_bytes(len_bytes.try_into().unwrap());
+        let len = usize::try_from(len).map_err(|_| truncated)?;
+        let byte_len = len.checked_add(7).ok_or(truncated)? / 8;
+        if body.len() < byte_len {
+            return Err(truncated)
+        }
+        if body.len() > byte_len {
+            return Err(InvalidBlockEncoding {
+                reason: "bytes left over after the recorded length",
+            })
+        }
+        let mut bits = Bits::from_be_bytes(body);
+        if bits.iter().skip(len).any(|bit| bit.0) {
+            return Err(InvalidBlockEncoding {
+                reason: "padding bits are not zero",
+            })
+        }
+        bits.truncate(len);
+        Ok(Self { bits })
+    }
+}
+
+impl From<Bits> for EncodedBlocks {
+    /// Wraps bits laid out the way `BlockCodec::encode()` writes them.
+    #[inline]
+    fn from(bits: Bits) -> Self {
+        Self { bits }
+    }
+}
+
+/// Reads `width` bits at `position` as an unsigned value and moves past them.
+#[inline]
+fn read_value(bits: &Bits, position: &mut usize, width: usize) -> Result<u64, InvalidBlockEncoding> {
+    if *position + width > bits.len() {
+        return Err(InvalidBlockEncoding {
+            reason: "bits end in the middle of a block",
+        })
+    }
+    let value = if width == 0 { 0 } else { bits.field(*position, width as u32) };
+    *position += width;
+    Ok(value)
+}
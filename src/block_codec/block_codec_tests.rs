@@ -0,0 +1,156 @@
+use crate as bitman;
+use bitman::{BitMan, Bits, BlockCodec, BlockTransform, EncodedBlocks, InvalidBlockEncoding};
+use proptest::prelude::*;
+
+const HEADER_BITS: usize = 64;
+const BLOCK_HEADER_BITS: usize = 8 + 64;
+
+#[test]
+fn plain_block_uses_width_of_largest_value() {
+    let values: Vec<u64> = (0..128).map(|value| value % 32).collect();
+    let encoded = BlockCodec::new(BlockTransform::Plain).encode(&values);
+    assert_eq!(encoded.len(), HEADER_BITS + BLOCK_HEADER_BITS + 128 * 5);
+    assert_eq!(BlockCodec::new(BlockTransform::Plain).decode(&encoded), Ok(values));
+}
+
+#[test]
+fn frame_of_reference_subtracts_block_minimum() {
+    let values: Vec<u64> = (0..200).map(|value| 1_000_000 + value % 8).collect();
+    let codec = BlockCodec::new(BlockTransform::FrameOfReference);
+    let encoded = codec.encode(&values);
+    assert_eq!(
+        encoded.len(),
+        HEADER_BITS + 2 * BLOCK_HEADER_BITS + 200 * 3
+    );
+    assert_eq!(codec.decode(&encoded), Ok(values));
+}
+
+#[test]
+fn delta_packs_gaps_of_sorted_values() {
+    let values: Vec<u64> = (0..128).map(|value| value * 10).collect();
+    let codec = BlockCodec::new(BlockTransform::Delta);
+    let encoded = codec.encode(&values);
+    assert_eq!(encoded.len(), HEADER_BITS + BLOCK_HEADER_BITS + 128 * 4);
+    assert_eq!(codec.decode(&encoded), Ok(values));
+}
+
+#[test]
+#[should_panic]
+fn delta_of_decreasing_values_panics() {
+    let _ = BlockCodec::new(BlockTransform::Delta).encode(&[5, 4]);
+}
+
+#[test]
+fn zigzag_delta_handles_decreasing_values() {
+    let values = vec![100, 99, 101, 98, 102, 0, u64::MAX];
+    let codec = BlockCodec::new(BlockTransform::ZigZagDelta);
+    assert_eq!(codec.decode(&codec.encode(&values)), Ok(values));
+}
+
+#[test]
+fn constant_block_needs_no_payload() {
+    let values = vec![7; 128];
+    let codec = BlockCodec::new(BlockTransform::FrameOfReference);
+    let encoded = codec.encode(&values);
+    assert_eq!(encoded.len(), HEADER_BITS + BLOCK_HEADER_BITS);
+    assert_eq!(codec.decode(&encoded), Ok(values));
+}
+
+#[test]
+fn empty_input_is_only_a_header() {
+    let codec = BlockCodec::new(BlockTransform::Plain);
+    let encoded = codec.encode(&[]);
+    assert_eq!(encoded.len(), HEADER_BITS);
+    assert_eq!(codec.decode(&encoded), Ok(Vec::new()));
+}
+
+#[test]
+fn truncated_input_is_an_error() {
+    let codec = BlockCodec::new(BlockTransform::Plain);
+    let mut bits = codec.encode(&[1, 2, 3]).as_bits().clone();
+    bits.pop();
+    assert_eq!(
+        codec.decode(&EncodedBlocks::from(bits)),
+        Err(InvalidBlockEncoding { reason: "bits end in the middle of a block" })
+    );
+    assert!(codec.decode(&EncodedBlocks::from(Bits::new(&[]))).is_err());
+}
+
+#[test]
+fn trailing_bits_are_an_error() {
+    let codec = BlockCodec::new(BlockTransform::Plain);
+    let mut bits = codec.encode(&[1, 2, 3]).as_bits().clone();
+    bits.extend(Bits::from_be_bytes(&[0]).iter());
+    assert!(codec.decode(&EncodedBlocks::from(bits)).is_err());
+}
+
+#[test]
+fn malformed_blocks_are_errors() {
+    let mut too_wide = 1u64.bits();
+    too_wide.extend(65u8.bits().iter());
+    too_wide.extend(0u64.bits().iter());
+    assert_eq!(
+        BlockCodec::new(BlockTransform::Plain).decode(&EncodedBlocks::from(too_wide)),
+        Err(InvalidBlockEncoding { reason: "block width above 64 bits" })
+    );
+    let mut overflowing = 1u64.bits();
+    overflowing.extend(1u8.bits().iter());
+    overflowing.extend(u64::MAX.bits().iter());
+    overflowing.push(bitman::Bit(true));
+    assert!(BlockCodec::new(BlockTransform::FrameOfReference)
+        .decode(&EncodedBlocks::from(overflowing))
+        .is_err());
+}
+
+#[test]
+fn bytes_record_the_bit_length() {
+    let codec = BlockCodec::new(BlockTransform::Delta);
+    let values: Vec<u64> = (0..300).map(|value| value * 3).collect();
+    let encoded = codec.encode(&values);
+    let bytes = encoded.to_bytes();
+    assert_eq!(bytes.len(), 8 + (encoded.len() + 7) / 8);
+    assert_eq!(bytes[..8], (encoded.len() as u64).to_be_bytes());
+    assert_eq!(EncodedBlocks::from_bytes(&bytes).as_ref(), Ok(&encoded));
+    assert_eq!(codec.decode(&EncodedBlocks::from_bytes(&bytes).unwrap()), Ok(values));
+}
+
+#[test]
+fn malformed_bytes_are_errors() {
+    let bytes = BlockCodec::new(BlockTransform::Plain).encode(&[5, 6, 7]).to_bytes();
+    assert!(EncodedBlocks::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(EncodedBlocks::from_bytes(&bytes[..4]).is_err());
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert!(EncodedBlocks::from_bytes(&extra).is_err());
+    let mut dirty_padding = bytes;
+    *dirty_padding.last_mut().unwrap() |= 1;
+    assert_eq!(
+        EncodedBlocks::from_bytes(&dirty_padding),
+        Err(InvalidBlockEncoding { reason: "padding bits are not zero" })
+    );
+}
+
+#[test]
+fn huge_recorded_length_is_an_error() {
+    let bytes = [u8::MAX; 9];
+    assert_eq!(
+        EncodedBlocks::from_bytes(&bytes),
+        Err(InvalidBlockEncoding { reason: "bytes end before the recorded length" })
+    );
+}
+
+proptest! {
+    #[test]
+    fn every_transform_round_trips(values in proptest::collection::vec(any::<u64>(), 0..400)) {
+        for transform in [BlockTransform::Plain, BlockTransform::FrameOfReference, BlockTransform::ZigZagDelta] {
+            let codec = BlockCodec::new(transform);
+            let encoded = codec.encode(&values);
+            prop_assert_eq!(codec.decode(&encoded), Ok(values.clone()));
+            prop_assert_eq!(codec.decode(&EncodedBlocks::from_bytes(&encoded.to_bytes()).unwrap()), Ok(values.clone()));
+        }
+        let mut sorted = values.clone();
+        sorted.sort_unstable();
+        let codec = BlockCodec::new(BlockTransform::Delta);
+        prop_assert_eq!(codec.decode(&codec.encode(&sorted)), Ok(sorted));
+    }
+}
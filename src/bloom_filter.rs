@@ -0,0 +1,304 @@
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::string::String;
+use core::f64::consts::LN_2;
+use num_traits::Float;
+
+use crate::{Bit, Bits};
+
+#[cfg(test)]
+mod bloom_filter_tests;
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Items a `BloomFilter` can hold.
+///
+/// Items are hashed from a byte encoding that is the same on every target,
+/// so a filter saved with `to_be_bytes()` answers alike wherever it is
+/// loaded.  Integers are their little endian bytes, with `usize` and
+/// `isize` widened to 64 bits, `char` is its code point as a `u32`, `bool`
+/// is one byte and strings and byte slices are their bytes.
+pub trait BloomItem {
+    /// Passes the item's encoding to `write`, possibly in several pieces.
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8]));
+}
+
+impl<T: BloomItem + ?Sized> BloomItem for &T {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        (**self).write_bloom_bytes(write);
+    }
+}
+
+impl BloomItem for [u8] {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(self);
+    }
+}
+
+impl<const N: usize> BloomItem for [u8; N] {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(self);
+    }
+}
+
+impl BloomItem for Vec<u8> {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(self);
+    }
+}
+
+impl BloomItem for str {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(self.as_bytes());
+    }
+}
+
+impl BloomItem for String {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(self.as_bytes());
+    }
+}
+
+impl BloomItem for bool {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(&[u8::from(*self)]);
+    }
+}
+
+impl BloomItem for char {
+    #[inline]
+    fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+        write(&u32::from(*self).to_le_bytes());
+    }
+}
+
+macro_rules! impl_bloom_item_for_integers {
+    ($($integer_type:ty => $encoded_type:ty$(,)?)*) => {$(
+        impl BloomItem for $integer_type {
+            #[inline]
+            fn write_bloom_bytes(&self, write: &mut dyn FnMut(&[u8])) {
+                write(&(*self as $encoded_type).to_le_bytes());
+            }
+        }
+    )*}
+}
+
+impl_bloom_item_for_integers!(
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => u64,
+    i8 => i8,
+    i16 => i16,
+    i32 => i32,
+    i64 => i64,
+    i128 => i128,
+    isize => i64,
+);
+
+/// The SplitMix64 finalizer, spreading FNV's output across all 64 bits.
+#[inline]
+fn mix(mut hash: u64) -> u64 {
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^ (hash >> 31)
+}
+
+/// A probabilistic set over a `Bits` of `bit_len()` bits, each item setting
+/// `hash_count()` of them.
+///
+/// `contains()` never misses an inserted item but may report items that
+/// were never inserted.  Filters with the same size and hash count can be
+/// combined with `union_with()` and `intersect_with()`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BloomFilter {
+    bits: Bits,
+    hash_count: u32,
+}
+
+impl BloomFilter {
+    /// An empty filter of `bit_len` bits setting `hash_count` bits per item.
+    #[must_use] pub fn new(bit_len: usize, hash_count: u32) -> Self {
+        assert!(bit_len > 0, "BloomFilter needs at least one bit");
+        assert!(hash_count > 0, "BloomFilter needs at least one hash function");
+        Self {
+            bits: Bits::new(&vec![Bit(false); bit_len]),
+            hash_count,
+        }
+    }
+
+    /// An empty filter sized so that holding `expected_items` items gives
+    /// roughly `false_positive_rate` false positives.
+    #[must_use] pub fn with_false_positive_rate(expected_items: usize, false_positive_rate: f64) -> Self {
+        let bit_len = Self::optimal_bit_len(expected_items, false_positive_rate);
+        Self::new(bit_len, Self::optimal_hash_count(bit_len, expected_items))
+    }
+
+    /// Bits needed for `expected_items` items at `false_positive_rate`.
+    #[must_use] pub fn optimal_bit_len(expected_items: usize, false_positive_rate: f64) -> usize {
+        assert!(
+            false_positive_rate > 0.0 && false_positive_rate < 1.0,
+            "False positive rate must be between 0 and 1"
+        );
+        let items = expected_items.max(1) as f64;
+        Float::ceil(-items * Float::ln(false_positive_rate) / (LN_2 * LN_2)).max(1.0) as usize
+    }
+
+    /// Hash functions minimizing false positives with `bit_len` bits and
+    /// `expected_items` items.
+    #[must_use] pub fn optimal_hash_count(bit_len: usize, expected_items: usize) -> u32 {
+        let ratio = bit_len as f64 / expected_items.max(1) as f64;
+        Float::round(ratio * LN_2).max(1.0) as u32
+    }
+
+    #[inline]
+    #[must_use] pub fn bit_len(&self) -> usize {
+        self.bits.len()
+    }
+
+    #[inline]
+    #[must_use] pub fn hash_count(&self) -> u32 {
+        self.hash_count
+    }
+
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.bits.first_one().is_none()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits = Bits::new(&vec![Bit(false); self.bits.len()]);
+    }
+
+    /// Adds `item`, returning whether it was not already reported as
+    /// present.
+    pub fn insert<T: BloomItem + ?Sized>(&mut self, item: &T) -> bool {
+        let mut newly_inserted = false;
+        for index in self.bit_indices(item) {
+            if !self.bits[index].0 {
+                newly_inserted = true;
+                self.bits.set(index, Bit(true));
+            }
+        }
+        newly_inserted
+    }
+
+    /// Whether `item` may have been inserted.  `false` is always correct.
+    #[must_use] pub fn contains<T: BloomItem + ?Sized>(&self, item: &T) -> bool {
+        self.bit_indices(item).all(|index| self.bits[index].0)
+    }
+
+    /// Whether both filters have the same size and hash count, so that
+    /// their bits can be combined.
+    #[inline]
+    #[must_use] pub fn is_compatible(&self, other: &Self) -> bool {
+        self.bits.len() == other.bits.len() && self.hash_count == other.hash_count
+    }
+
+    /// Adds every item of `other`, as if each had been inserted here.
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        self.assert_compatible(other);
+        self.bits |= other.bits.clone();
+    }
+
+    /// Keeps only the bits also set in `other`.  Items in both filters are
+    /// still reported present, with a false positive rate no higher than
+    /// either filter's.
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.assert_compatible(other);
+        self.bits &= other.bits.clone();
+    }
+
+    #[inline]
+    #[must_use] pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.union_with(other);
+        union
+    }
+
+    #[inline]
+    #[must_use] pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = self.clone();
+        intersection.intersect_with(other);
+        intersection
+    }
+
+    /// Estimated number of distinct items inserted, from the fraction of
+    /// bits set.  Infinite once every bit is set.
+    #[must_use] pub fn estimated_len(&self) -> f64 {
+        let bit_len = self.bits.len() as f64;
+        let zero_fraction = self.bits.count_zeros() as f64 / bit_len;
+        -bit_len / f64::from(self.hash_count) * Float::ln(zero_fraction)
+    }
+
+    /// Probability that `contains()` reports an item that was never
+    /// inserted, given the bits currently set.
+    #[must_use] pub fn estimated_false_positive_rate(&self) -> f64 {
+        let one_fraction = self.bits.count_ones() as f64 / self.bits.len() as f64;
+        Float::powi(one_fraction, self.hash_count as i32)
+    }
+
+    /// The filter's bits, packed with `Bits::to_be_bytes()`.
+    #[inline]
+    #[must_use] pub fn to_be_bytes(&self) -> Vec<u8> {
+        self.bits.to_be_bytes()
+    }
+
+    /// Rebuilds a filter from `to_be_bytes()` output and the `bit_len()` and
+    /// `hash_count()` it was created with.
+    #[must_use] pub fn from_be_bytes(bytes: &[u8], bit_len: usize, hash_count: u32) -> Self {
+        assert_eq!(
+            bytes.len(),
+            (bit_len + 7) / 8,
+            "Wrong number of bytes for a BloomFilter of {bit_len} bits"
+        );
+        let padded = Bits::from_be_bytes(bytes);
+        let mut bloom_filter = Self::new(bit_len, hash_count);
+        bloom_filter.bits = padded.slice(padded.len() - bit_len..);
+        bloom_filter
+    }
+
+    #[inline]
+    fn assert_compatible(&self, other: &Self) {
+        assert!(
+            self.is_compatible(other),
+            "BloomFilters differ in size or hash count"
+        );
+    }
+
+    /// The bits for `item`, by double hashing the 64 bit FNV-1a hash of its
+    /// encoding.
+    #[inline]
+    fn bit_indices<T: BloomItem + ?Sized>(&self, item: &T) -> impl Iterator<Item = usize> {
+        let mut hash = FNV_OFFSET_BASIS;
+        item.write_bloom_bytes(&mut |bytes| {
+            for byte in bytes {
+                hash = (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME);
+            }
+        });
+        let first = mix(hash);
+        let step = mix(hash ^ 0x9E37_79B9_7F4A_7C15) | 1;
+        let bit_len = self.bits.len() as u64;
+        (0..u64::from(self.hash_count))
+            .map(move |round| (first.wrapping_add(round.wrapping_mul(step)) % bit_len) as usize)
+    }
+}
@@ -0,0 +1,99 @@
+use crate as bitman;
+use bitman::BloomFilter;
+use proptest::prelude::*;
+
+#[test]
+fn sizing_from_false_positive_rate() {
+    assert_eq!(BloomFilter::optimal_bit_len(1_000, 0.01), 9_586);
+    assert_eq!(BloomFilter::optimal_hash_count(9_586, 1_000), 7);
+    let bloom_filter = BloomFilter::with_false_positive_rate(1_000, 0.01);
+    assert_eq!(bloom_filter.bit_len(), 9_586);
+    assert_eq!(bloom_filter.hash_count(), 7);
+    assert!(bloom_filter.is_empty());
+}
+
+#[test]
+fn false_positive_rate_stays_near_target() {
+    let mut bloom_filter = BloomFilter::with_false_positive_rate(1_000, 0.01);
+    for item in 0..1_000u32 {
+        bloom_filter.insert(&item);
+    }
+    let false_positives = (1_000..11_000u32).filter(|item| bloom_filter.contains(item)).count();
+    assert!(false_positives < 250, "{false_positives} false positives");
+    assert!(bloom_filter.estimated_false_positive_rate() < 0.02);
+    let estimate = bloom_filter.estimated_len();
+    assert!((950.0..1_050.0).contains(&estimate), "estimated {estimate} items");
+}
+
+#[test]
+fn insert_reports_new_items() {
+    let mut bloom_filter = BloomFilter::new(512, 3);
+    assert!(bloom_filter.insert("alpha"));
+    assert!(!bloom_filter.insert("alpha"));
+    assert!(bloom_filter.contains("alpha"));
+    bloom_filter.clear();
+    assert!(!bloom_filter.contains("alpha"));
+}
+
+#[test]
+fn union_and_intersection_of_compatible_filters() {
+    let mut lhs = BloomFilter::new(2_048, 4);
+    let mut rhs = BloomFilter::new(2_048, 4);
+    lhs.insert(&1u64);
+    lhs.insert(&2u64);
+    rhs.insert(&2u64);
+    rhs.insert(&3u64);
+    let union = lhs.union(&rhs);
+    assert!([1u64, 2, 3].iter().all(|item| union.contains(item)));
+    let intersection = lhs.intersection(&rhs);
+    assert!(intersection.contains(&2u64));
+    assert!(!intersection.contains(&1u64) || !intersection.contains(&3u64));
+}
+
+#[test]
+#[should_panic(expected = "differ")]
+fn rejects_incompatible_filters() {
+    let mut lhs = BloomFilter::new(64, 2);
+    lhs.union_with(&BloomFilter::new(64, 3));
+}
+
+#[test]
+fn round_trips_through_bytes() {
+    let mut bloom_filter = BloomFilter::new(1_001, 5);
+    for item in ["a", "b", "c"] {
+        bloom_filter.insert(item);
+    }
+    let bytes = bloom_filter.to_be_bytes();
+    assert_eq!(bytes.len(), 126);
+    assert_eq!(BloomFilter::from_be_bytes(&bytes, 1_001, 5), bloom_filter);
+}
+
+#[test]
+fn items_hash_the_same_on_every_target() {
+    let mut bloom_filter = BloomFilter::new(64, 3);
+    bloom_filter.insert("bitman");
+    bloom_filter.insert(&7u32);
+    bloom_filter.insert(&-1i16);
+    bloom_filter.insert(&'x');
+    assert_eq!(bloom_filter.to_be_bytes(), [65, 128, 16, 0, 32, 40, 0, 186]);
+    let mut widened = BloomFilter::new(4_096, 4);
+    widened.insert(&12_345usize);
+    assert!(widened.contains(&12_345u64));
+    widened.insert(&-5isize);
+    assert!(widened.contains(&-5i64));
+    widened.insert(&"bytes".to_owned());
+    assert!(widened.contains(b"bytes"));
+}
+
+proptest! {
+    #[test]
+    fn never_misses_inserted_items(items in proptest::collection::vec(any::<u64>(), 0..200), bit_len in 1usize..4_000, hash_count in 1u32..10) {
+        let mut bloom_filter = BloomFilter::new(bit_len, hash_count);
+        for item in &items {
+            bloom_filter.insert(item);
+        }
+        prop_assert!(items.iter().all(|item| bloom_filter.contains(item)));
+        let bytes = bloom_filter.to_be_bytes();
+        prop_assert_eq!(BloomFilter::from_be_bytes(&bytes, bit_len, hash_count), bloom_filter);
+    }
+}
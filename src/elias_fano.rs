@@ -0,0 +1,185 @@
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::mem::size_of_val;
+
+use crate::scanning::find_first;
+use crate::{Bits, RankSelect};
+
+#[cfg(test)]
+mod elias_fano_tests;
+
+/// A non-decreasing sequence of `u64` values in Elias-Fano encoding.
+///
+/// Each value is split into `low_width` low bits, kept back to back in a
+/// `Bits`, and the remaining high bits, stored in unary in
+/// `high_bits()`: value `i` sets bit `(value >> low_width) + i`.  That takes
+/// about `2 + low_width` bits per value plus the rank tables.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct EliasFano {
+    low_bits: Bits,
+    high_bits: RankSelect,
+    low_width: u32,
+    len: usize,
+}
+
+impl EliasFano {
+    #[must_use] pub fn new(values: &[u64]) -> Self {
+        assert!(
+            values.windows(2).all(|pair| pair[0] <= pair[1]),
+            "Values passed to EliasFano::new() must be non-decreasing"
+        );
+        let len = values.len();
+        let last = values.last().copied().unwrap_or(0);
+        let low_width = if len == 0 {
+            0
+        } else {
+            let ratio = ((u128::from(last) + 1) / len as u128).max(1);
+            (127 - ratio.leading_zeros()).min(63)
+        };
+
+        let high_len = (last >> low_width) as usize + len + 1;
+        let mut low_bits = Bits::zeroed(len * low_width as usize);
+        let mut high_words = vec![0u64; (high_len + 63) / 64];
+        for (index, &value) in values.iter().enumerate() {
+            if low_width > 0 {
+                low_bits.set_field(index * low_width as usize, low_width, value);
+            }
+            let high_position = (value >> low_width) as usize + index;
+            high_words[high_position / 64] |= 1 << (high_position % 64);
+        }
+        Self {
+            low_bits,
+            high_bits: RankSelect::from_words(high_words, high_len),
+            low_width,
+            len,
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    #[must_use] pub fn low_width(&self) -> u32 {
+        self.low_width
+    }
+
+    /// The unary coded high bits, with their rank tables.
+    #[inline]
+    #[must_use] pub fn high_bits(&self) -> &RankSelect {
+        &self.high_bits
+    }
+
+    /// Bytes used by the low bits, the high bits and their rank tables.
+    #[inline]
+    #[must_use] pub fn size_in_bytes(&self) -> usize {
+        size_of_val(self.low_bits.words()) + self.high_bits.size_in_bytes()
+    }
+
+    #[inline]
+    #[must_use] pub fn get(&self, index: usize) -> Option<u64> {
+        let high_position = self.high_bits.select1(index)?;
+        Some(self.value(index, high_position))
+    }
+
+    /// The first value not less than `target`, with its index.
+    #[must_use] pub fn next_geq(&self, target: u64) -> Option<(usize, u64)> {
+        let bucket = target >> self.low_width;
+        let mut position = if bucket == 0 {
+            0
+        } else {
+            self.high_bits.select0(bucket as usize - 1)? + 1
+        };
+        let mut index = position - bucket as usize;
+        while index < self.len {
+            position = self.next_high_one(position)?;
+            let value = self.value(index, position);
+            if value >= target {
+                return Some((index, value))
+            }
+            index += 1;
+            position += 1;
+        }
+        None
+    }
+
+    #[inline]
+    #[must_use] pub fn iter(&self) -> EliasFanoIter<'_> {
+        EliasFanoIter {
+            elias_fano: self,
+            word_index: 0,
+            word: self.high_bits.words().first().copied().unwrap_or(0),
+            index: 0,
+        }
+    }
+
+    #[inline]
+    fn value(&self, index: usize, high_position: usize) -> u64 {
+        let low_value = if self.low_width == 0 {
+            0
+        } else {
+            self.low_bits.field(index * self.low_width as usize, self.low_width)
+        };
+        (((high_position - index) as u64) << self.low_width) | low_value
+    }
+
+    /// Position of the first high one at or after `position`.
+    #[inline]
+    fn next_high_one(&self, position: usize) -> Option<usize> {
+        find_first(self.high_bits.words(), position, self.high_bits.len(), true)
+    }
+}
+
+impl FromIterator<u64> for EliasFano {
+    #[inline]
+    fn from_iter<Values: IntoIterator<Item = u64>>(values: Values) -> Self {
+        Self::new(&values.into_iter().collect::<Vec<u64>>())
+    }
+}
+
+impl<'a> IntoIterator for &'a EliasFano {
+    type Item = u64;
+    type IntoIter = EliasFanoIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the values of an `EliasFano` in order.
+#[derive(Debug, Clone)]
+pub struct EliasFanoIter<'a> {
+    elias_fano: &'a EliasFano,
+    word_index: usize,
+    word: u64,
+    index: usize,
+}
+
+impl Iterator for EliasFanoIter<'_> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        let words = self.elias_fano.high_bits.words();
+        while self.word == 0 {
+            self.word_index += 1;
+            self.word = *words.get(self.word_index)?;
+        }
+        let high_position = self.word_index * 64 + self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        let value = self.elias_fano.value(self.index, high_position);
+        self.index += 1;
+        Some(value)
+    }
+}
+
+impl FusedIterator for EliasFanoIter<'_> {}
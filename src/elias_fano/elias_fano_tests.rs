@@ -0,0 +1,88 @@
+use crate as bitman;
+use std::mem::size_of;
+use bitman::EliasFano;
+use proptest::prelude::*;
+
+#[test]
+fn get_returns_encoded_values() {
+    let values = [2, 3, 5, 7, 11, 13, 24];
+    let elias_fano = EliasFano::new(&values);
+    assert_eq!(elias_fano.len(), 7);
+    assert_eq!(elias_fano.low_width(), 1);
+    for (index, &value) in values.iter().enumerate() {
+        assert_eq!(elias_fano.get(index), Some(value));
+    }
+    assert_eq!(elias_fano.get(7), None);
+}
+
+#[test]
+fn bit_arrays_have_expected_sizes() {
+    let elias_fano = EliasFano::new(&[2, 3, 5, 7, 11, 13, 24]);
+    assert_eq!(elias_fano.high_bits().len(), (24 >> 1) + 7 + 1);
+    assert_eq!(elias_fano.high_bits().count_ones(), 7);
+}
+
+#[test]
+fn size_is_close_to_the_elias_fano_bound() {
+    let values: Vec<u64> = (0..10_000u64).map(|index| index * 100 + index % 7).collect();
+    let elias_fano = EliasFano::new(&values);
+    assert_eq!(elias_fano.low_width(), 6);
+    let bound_in_bytes = values.len() * (2 + 6) / 8;
+    assert!(elias_fano.size_in_bytes() < bound_in_bytes * 5 / 4);
+    assert!(elias_fano.size_in_bytes() < values.len() * size_of::<u64>() / 6);
+}
+
+#[test]
+fn next_geq_finds_successor() {
+    let elias_fano = EliasFano::new(&[2, 3, 5, 7, 11, 13, 24]);
+    assert_eq!(elias_fano.next_geq(0), Some((0, 2)));
+    assert_eq!(elias_fano.next_geq(5), Some((2, 5)));
+    assert_eq!(elias_fano.next_geq(8), Some((4, 11)));
+    assert_eq!(elias_fano.next_geq(14), Some((6, 24)));
+    assert_eq!(elias_fano.next_geq(25), None);
+}
+
+#[test]
+fn duplicates_and_zero_are_kept() {
+    let elias_fano: EliasFano = vec![0, 0, 4, 4, 4, 9].into_iter().collect();
+    assert_eq!(elias_fano.iter().collect::<Vec<u64>>(), vec![0, 0, 4, 4, 4, 9]);
+    assert_eq!(elias_fano.next_geq(1), Some((2, 4)));
+}
+
+#[test]
+fn empty_sequence() {
+    let elias_fano = EliasFano::new(&[]);
+    assert!(elias_fano.is_empty());
+    assert_eq!(elias_fano.get(0), None);
+    assert_eq!(elias_fano.next_geq(0), None);
+    assert_eq!(elias_fano.iter().next(), None);
+}
+
+#[test]
+fn extreme_values_round_trip() {
+    let values = [0, u64::MAX];
+    let elias_fano = EliasFano::new(&values);
+    assert_eq!(elias_fano.iter().collect::<Vec<u64>>(), values.to_vec());
+    assert_eq!(elias_fano.next_geq(1), Some((1, u64::MAX)));
+    assert_eq!(EliasFano::new(&[u64::MAX]).get(0), Some(u64::MAX));
+}
+
+#[test]
+#[should_panic]
+fn decreasing_values_panic() {
+    let _ = EliasFano::new(&[3, 2]);
+}
+
+proptest! {
+    #[test]
+    fn matches_sorted_vector(mut values in proptest::collection::vec(0u64..100_000, 0..300), target in 0u64..110_000) {
+        values.sort_unstable();
+        let elias_fano = EliasFano::new(&values);
+        prop_assert_eq!((&elias_fano).into_iter().collect::<Vec<u64>>(), values.clone());
+        for (index, &value) in values.iter().enumerate() {
+            prop_assert_eq!(elias_fano.get(index), Some(value));
+        }
+        let expected = values.iter().position(|&value| value >= target).map(|index| (index, values[index]));
+        prop_assert_eq!(elias_fano.next_geq(target), expected);
+    }
+}
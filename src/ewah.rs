@@ -0,0 +1,456 @@
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::{Bit, Bits};
+
+#[cfg(test)]
+mod ewah_tests;
+
+const RUN_LENGTH_BITS: u32 = 32;
+const LITERAL_COUNT_BITS: u32 = 31;
+const MAX_RUN_LENGTH: u64 = (1 << RUN_LENGTH_BITS) - 1;
+const MAX_LITERAL_COUNT: u64 = (1 << LITERAL_COUNT_BITS) - 1;
+
+/// A bit vector compressed with the Enhanced Word-Aligned Hybrid scheme.
+///
+/// The bits are split into 64 bit words, bit `i` landing in bit `i % 64` of
+/// word `i / 64`.  Stretches of all-zero or all-one words collapse into a
+/// marker word holding the run's value and length in words (bit 0 and bits
+/// 1 to 32) and how many literal words follow the run (bits 33 to 63).
+/// Logical operators work on the compressed words directly and, like the
+/// `Bits` operators, produce a result as long as the shorter operand.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct EwahBitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl EwahBitmap {
+    #[inline]
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `len` bits, all set to `bit`.
+    #[must_use] pub fn filled(bit: Bit, len: usize) -> Self {
+        let mut builder = EwahBuilder::new(len);
+        builder.push_run(bit.0, builder.total_words);
+        builder.finish()
+    }
+
+    /// Number of bits, set or not.
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The compressed marker and literal words.
+    #[inline]
+    #[must_use] pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    #[inline]
+    #[must_use] pub fn size_in_bytes(&self) -> usize {
+        self.words.len() * 8
+    }
+
+    #[must_use] pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            return None
+        }
+        let target_word = (index / 64) as u64;
+        let mut word_position = 0u64;
+        let mut chunks = Chunks::new(&self.words);
+        while let Some(chunk) = chunks.peek() {
+            match chunk {
+                Chunk::Run(bit, run_length) => {
+                    if target_word < word_position + run_length {
+                        return Some(Bit(bit))
+                    }
+                    chunks.advance(run_length);
+                    word_position += run_length;
+                }
+                Chunk::Literal(word) => {
+                    if target_word == word_position {
+                        return Some(Bit((word >> (index % 64)) & 1 == 1))
+                    }
+                    chunks.advance(1);
+                    word_position += 1;
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    #[must_use] pub fn count_ones(&self) -> usize {
+        let mut ones = 0usize;
+        let mut chunks = Chunks::new(&self.words);
+        while let Some(chunk) = chunks.peek() {
+            match chunk {
+                Chunk::Run(bit, run_length) => {
+                    if bit {
+                        ones += run_length as usize * 64;
+                    }
+                    chunks.advance(run_length);
+                }
+                Chunk::Literal(word) => {
+                    ones += word.count_ones() as usize;
+                    chunks.advance(1);
+                }
+            }
+        }
+        ones
+    }
+
+    #[inline]
+    #[must_use] pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    /// Positions of the set bits in increasing order.  Runs of zero words
+    /// are skipped without being expanded.
+    #[inline]
+    #[must_use] pub fn iter_ones(&self) -> EwahOnes<'_> {
+        EwahOnes {
+            chunks: Chunks::new(&self.words),
+            word_position: 0,
+            run_end: 0,
+            next_in_run: 0,
+            literal: 0,
+            literal_position: 0,
+        }
+    }
+
+    #[must_use] pub fn to_bits(&self) -> Bits {
+        let mut bits = Bits::zeroed(self.len);
+        for index in self.iter_ones() {
+            bits.set(index, Bit(true));
+        }
+        bits
+    }
+
+    fn combine(&self, other: &Self, operation: fn(u64, u64) -> u64) -> Self {
+        let mut builder = EwahBuilder::new(self.len.min(other.len));
+        let mut lhs = Chunks::new(&self.words);
+        let mut rhs = Chunks::new(&other.words);
+        while builder.word_count < builder.total_words {
+            let remaining = builder.total_words - builder.word_count;
+            match (lhs.peek(), rhs.peek()) {
+                (Some(Chunk::Run(lhs_bit, lhs_length)), Some(Chunk::Run(rhs_bit, rhs_length))) => {
+                    let run_length = lhs_length.min(rhs_length).min(remaining);
+                    let word = operation(fill_word(lhs_bit), fill_word(rhs_bit));
+                    builder.push_run(word != 0, run_length);
+                    lhs.advance(run_length);
+                    rhs.advance(run_length);
+                }
+                (Some(lhs_chunk), Some(rhs_chunk)) => {
+                    builder.push_literal(operation(lhs_chunk.first_word(), rhs_chunk.first_word()));
+                    lhs.advance(1);
+                    rhs.advance(1);
+                }
+                _ => unreachable!(),
+            }
+        }
+        builder.finish()
+    }
+}
+
+#[inline]
+fn fill_word(bit: bool) -> u64 {
+    if bit {
+        u64::MAX
+    } else {
+        0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Chunk {
+    /// A run of identical words: their bit value and how many there are.
+    Run(bool, u64),
+    /// A single literal word.
+    Literal(u64),
+}
+
+impl Chunk {
+    #[inline]
+    fn first_word(self) -> u64 {
+        match self {
+            Self::Run(bit, _) => fill_word(bit),
+            Self::Literal(word) => word,
+        }
+    }
+}
+
+/// Walks the compressed words as runs and literals.
+#[derive(Debug, Clone)]
+struct Chunks<'a> {
+    words: &'a [u64],
+    next_index: usize,
+    run_bit: bool,
+    run_remaining: u64,
+    literals_remaining: u64,
+}
+
+impl<'a> Chunks<'a> {
+    #[inline]
+    fn new(words: &'a [u64]) -> Self {
+        Self {
+            words,
+            next_index: 0,
+            run_bit: false,
+            run_remaining: 0,
+            literals_remaining: 0,
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<Chunk> {
+        while self.run_remaining == 0 && self.literals_remaining == 0 {
+            let marker = *self.words.get(self.next_index)?;
+            self.next_index += 1;
+            self.run_bit = marker & 1 == 1;
+            self.run_remaining = (marker >> 1) & MAX_RUN_LENGTH;
+            self.literals_remaining = marker >> (RUN_LENGTH_BITS + 1);
+        }
+        if self.run_remaining > 0 {
+            Some(Chunk::Run(self.run_bit, self.run_remaining))
+        } else {
+            Some(Chunk::Literal(self.words[self.next_index]))
+        }
+    }
+
+    /// Skips `word_count` words, which must not extend past the current
+    /// run, or be 1 when the current chunk is a literal.
+    #[inline]
+    fn advance(&mut self, word_count: u64) {
+        if self.run_remaining > 0 {
+            self.run_remaining -= word_count;
+        } else {
+            self.next_index += 1;
+            self.literals_remaining -= 1;
+        }
+    }
+}
+
+/// Appends runs and literal words, merging them into marker words and
+/// keeping the bits past `len` clear.
+struct EwahBuilder {
+    words: Vec<u64>,
+    marker_index: usize,
+    word_count: u64,
+    total_words: u64,
+    tail_mask: u64,
+    len: usize,
+}
+
+impl EwahBuilder {
+    #[inline]
+    fn new(len: usize) -> Self {
+        let tail_mask = match len % 64 {
+            0 => u64::MAX,
+            tail_bits => (1 << tail_bits) - 1,
+        };
+        Self {
+            words: vec![0],
+            marker_index: 0,
+            word_count: 0,
+            total_words: ((len + 63) / 64) as u64,
+            tail_mask,
+            len,
+        }
+    }
+
+    #[inline]
+    fn marker_run(&self) -> (bool, u64, u64) {
+        let marker = self.words[self.marker_index];
+        (marker & 1 == 1, (marker >> 1) & MAX_RUN_LENGTH, marker >> (RUN_LENGTH_BITS + 1))
+    }
+
+    #[inline]
+    fn set_marker(&mut self, bit: bool, run_length: u64, literal_count: u64) {
+        self.words[self.marker_index] =
+            u64::from(bit) | (run_length << 1) | (literal_count << (RUN_LENGTH_BITS + 1));
+    }
+
+    #[inline]
+    fn start_marker(&mut self) {
+        self.marker_index = self.words.len();
+        self.words.push(0);
+    }
+
+    fn push_run(&mut self, bit: bool, mut run_length: u64) {
+        if bit && self.tail_mask != u64::MAX && run_length > 0 && self.word_count + run_length == self.total_words {
+            self.push_run(true, run_length - 1);
+            self.push_literal(self.tail_mask);
+            return
+        }
+        self.word_count += run_length;
+        while run_length > 0 {
+            let (marker_bit, marker_length, literal_count) = self.marker_run();
+            let extendable = literal_count == 0 && (marker_length == 0 || marker_bit == bit);
+            if !extendable || marker_length == MAX_RUN_LENGTH {
+                self.start_marker();
+                continue
+            }
+            let added = run_length.min(MAX_RUN_LENGTH - marker_length);
+            self.set_marker(bit, marker_length + added, 0);
+            run_length -= added;
+        }
+    }
+
+    fn push_literal(&mut self, mut word: u64) {
+        if self.word_count + 1 == self.total_words {
+            word &= self.tail_mask;
+        }
+        if word == 0 || word == u64::MAX {
+            self.push_run(word != 0, 1);
+            return
+        }
+        let (marker_bit, marker_length, literal_count) = self.marker_run();
+        if literal_count == MAX_LITERAL_COUNT {
+            self.start_marker();
+            self.set_marker(false, 0, 1);
+        } else {
+            self.set_marker(marker_bit, marker_length, literal_count + 1);
+        }
+        self.words.push(word);
+        self.word_count += 1;
+    }
+
+    #[inline]
+    fn finish(mut self) -> EwahBitmap {
+        if self.words == [0] {
+            self.words.clear();
+        }
+        EwahBitmap {
+            words: self.words,
+            len: self.len,
+        }
+    }
+}
+
+impl From<&Bits> for EwahBitmap {
+    #[inline]
+    fn from(bits: &Bits) -> Self {
+        let mut builder = EwahBuilder::new(bits.len());
+        for &word in bits.words() {
+            builder.push_literal(word);
+        }
+        builder.finish()
+    }
+}
+
+impl From<&EwahBitmap> for Bits {
+    #[inline]
+    fn from(ewah_bitmap: &EwahBitmap) -> Bits {
+        ewah_bitmap.to_bits()
+    }
+}
+
+impl BitAnd for &EwahBitmap {
+    type Output = EwahBitmap;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> EwahBitmap {
+        self.combine(rhs, |lhs, rhs| lhs & rhs)
+    }
+}
+
+impl BitOr for &EwahBitmap {
+    type Output = EwahBitmap;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> EwahBitmap {
+        self.combine(rhs, |lhs, rhs| lhs | rhs)
+    }
+}
+
+impl BitXor for &EwahBitmap {
+    type Output = EwahBitmap;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> EwahBitmap {
+        self.combine(rhs, |lhs, rhs| lhs ^ rhs)
+    }
+}
+
+impl Not for &EwahBitmap {
+    type Output = EwahBitmap;
+
+    fn not(self) -> EwahBitmap {
+        let mut builder = EwahBuilder::new(self.len);
+        let mut chunks = Chunks::new(&self.words);
+        while let Some(chunk) = chunks.peek() {
+            match chunk {
+                Chunk::Run(bit, run_length) => {
+                    builder.push_run(!bit, run_length);
+                    chunks.advance(run_length);
+                }
+                Chunk::Literal(word) => {
+                    builder.push_literal(!word);
+                    chunks.advance(1);
+                }
+            }
+        }
+        builder.finish()
+    }
+}
+
+/// Iterator over the set bit positions of an `EwahBitmap`.
+#[derive(Debug, Clone)]
+pub struct EwahOnes<'a> {
+    chunks: Chunks<'a>,
+    word_position: usize,
+    run_end: usize,
+    next_in_run: usize,
+    literal: u64,
+    literal_position: usize,
+}
+
+impl Iterator for EwahOnes<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        loop {
+            if self.next_in_run < self.run_end {
+                self.next_in_run += 1;
+                return Some(self.next_in_run - 1)
+            }
+            if self.literal != 0 {
+                let index = self.literal_position + self.literal.trailing_zeros() as usize;
+                self.literal &= self.literal - 1;
+                return Some(index)
+            }
+            match self.chunks.peek()? {
+                Chunk::Run(bit, run_length) => {
+                    let run_start = self.word_position * 64;
+                    self.word_position += run_length as usize;
+                    if bit {
+                        self.next_in_run = run_start;
+                        self.run_end = self.word_position * 64;
+                    }
+                    self.chunks.advance(run_length);
+                }
+                Chunk::Literal(word) => {
+                    self.literal = word;
+                    self.literal_position = self.word_position * 64;
+                    self.word_position += 1;
+                    self.chunks.advance(1);
+                }
+            }
+        }
+    }
+}
+
+impl FusedIterator for EwahOnes<'_> {}
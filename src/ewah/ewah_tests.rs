@@ -0,0 +1,84 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits, EwahBitmap};
+use proptest::prelude::*;
+
+fn clustered_bits(len: usize, runs: &[(usize, usize)]) -> Bits {
+    let mut bits = Bits::new(&vec![Bit(false); len]);
+    for &(start, end) in runs {
+        for index in start..end {
+            bits.set(index, Bit(true));
+        }
+    }
+    bits
+}
+
+#[test]
+fn clustered_bits_compress_to_a_few_words() {
+    let bits = clustered_bits(100_000, &[(0, 40_000), (70_000, 70_003)]);
+    let ewah_bitmap = EwahBitmap::from(&bits);
+    assert_eq!(ewah_bitmap.len(), 100_000);
+    assert!(ewah_bitmap.as_words().len() <= 6);
+    assert_eq!(ewah_bitmap.count_ones(), 40_003);
+    assert_eq!(ewah_bitmap.count_zeros(), 59_997);
+    assert_eq!(ewah_bitmap.get(39_999), Some(Bit(true)));
+    assert_eq!(ewah_bitmap.get(40_000), Some(Bit(false)));
+    assert_eq!(ewah_bitmap.get(70_002), Some(Bit(true)));
+    assert_eq!(ewah_bitmap.get(100_000), None);
+    assert_eq!(Bits::from(&ewah_bitmap), bits);
+}
+
+#[test]
+fn filled_keeps_bits_past_the_end_clear() {
+    let ones = EwahBitmap::filled(Bit(true), 130);
+    assert_eq!(ones.count_ones(), 130);
+    assert_eq!(ones.iter_ones().last(), Some(129));
+    assert_eq!((!&ones).count_ones(), 0);
+    assert_eq!(!&ones, EwahBitmap::filled(Bit(false), 130));
+    assert!(EwahBitmap::new().is_empty());
+    assert_eq!(EwahBitmap::filled(Bit(true), 0), EwahBitmap::new());
+}
+
+#[test]
+fn operators_truncate_to_the_shorter_operand() {
+    let lhs = EwahBitmap::from(&clustered_bits(200, &[(0, 150)]));
+    let rhs = EwahBitmap::from(&clustered_bits(100, &[(90, 100)]));
+    assert_eq!((&lhs & &rhs).len(), 100);
+    assert_eq!((&lhs & &rhs).iter_ones().collect::<Vec<usize>>(), (90..100).collect::<Vec<usize>>());
+    assert_eq!((&lhs | &rhs).count_ones(), 100);
+    assert_eq!((&lhs ^ &rhs).iter_ones().collect::<Vec<usize>>(), (0..90).collect::<Vec<usize>>());
+}
+
+#[test]
+fn equal_bits_give_equal_encodings() {
+    let bits = clustered_bits(1_000, &[(64, 128), (300, 301)]);
+    let direct = EwahBitmap::from(&bits);
+    let rebuilt = &(&direct | &EwahBitmap::filled(Bit(false), 1_000)) & &EwahBitmap::filled(Bit(true), 1_000);
+    assert_eq!(rebuilt, direct);
+}
+
+proptest! {
+    #[test]
+    fn matches_dense_bits(
+        lhs_runs in proptest::collection::vec((0usize..3_000, 0usize..400), 0..6),
+        rhs_runs in proptest::collection::vec((0usize..3_000, 0usize..400), 0..6),
+        noise in proptest::collection::vec(0usize..3_000, 0..20),
+        len in 0usize..3_000,
+    ) {
+        let lhs_ranges: Vec<(usize, usize)> = lhs_runs.iter().map(|&(start, run)| (start, (start + run).min(3_000))).collect();
+        let rhs_ranges: Vec<(usize, usize)> = rhs_runs.iter().map(|&(start, run)| (start.min(len), (start + run).min(len))).collect();
+        let mut lhs_bits = clustered_bits(3_000, &lhs_ranges);
+        for index in noise {
+            lhs_bits.set(index, !lhs_bits[index]);
+        }
+        let rhs_bits = clustered_bits(len, &rhs_ranges);
+        let lhs = EwahBitmap::from(&lhs_bits);
+        let rhs = EwahBitmap::from(&rhs_bits);
+        prop_assert_eq!(Bits::from(&lhs), lhs_bits.clone());
+        prop_assert_eq!(lhs.iter_ones().collect::<Vec<usize>>(), lhs_bits.iter_ones().collect::<Vec<usize>>());
+        prop_assert_eq!(Bits::from(&(&lhs & &rhs)), lhs_bits.clone() & rhs_bits.clone());
+        prop_assert_eq!(Bits::from(&(&lhs | &rhs)), lhs_bits.clone() | rhs_bits.clone());
+        prop_assert_eq!(Bits::from(&(&lhs ^ &rhs)), lhs_bits.clone() ^ rhs_bits.clone());
+        prop_assert_eq!(Bits::from(&!&rhs).iter_ones().collect::<Vec<usize>>(), rhs_bits.iter_zeros().collect::<Vec<usize>>());
+        prop_assert_eq!(&lhs & &rhs, EwahBitmap::from(&(lhs_bits & rhs_bits)));
+    }
+}
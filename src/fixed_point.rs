@@ -0,0 +1,308 @@
+use core::cmp::Ordering;
+
+use crate::minifloat::power_of_two;
+use crate::{Bit, BitMan, Bits, FloatClass, FloatParts, RoundingMode};
+
+#[cfg(test)]
+mod fixed_point_tests;
+
+/// A binary fixed point number with `INTEGER` integer bits (including the
+/// sign bit when `SIGNED`) and `FRACTION` fractional bits, kept in a
+/// two's complement `Bits` of `INTEGER + FRACTION` bits.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct FixedPoint<const INTEGER: u32, const FRACTION: u32, const SIGNED: bool> {
+    bits: Bits,
+}
+
+/// Q15 (Q1.15), the 16 bit DSP sample format.
+pub type Q15 = FixedPoint<1, 15, true>;
+/// Q31 (Q1.31), the 32 bit DSP sample format.
+pub type Q31 = FixedPoint<1, 31, true>;
+
+impl<const INTEGER: u32, const FRACTION: u32, const SIGNED: bool> FixedPoint<INTEGER, FRACTION, SIGNED> {
+    const BIT_LEN: u32 = INTEGER + FRACTION;
+
+    #[inline]
+    #[must_use] pub fn new(bits: &Bits) -> Self {
+        assert!(
+            (1..=64).contains(&Self::BIT_LEN) && (!SIGNED || INTEGER >= 1),
+            "FixedPoint supports 1 to 64 bits and needs an integer bit for the sign"
+        );
+        assert_eq!(
+            bits.len(),
+            Self::BIT_LEN as usize,
+            "Bits passed to FixedPoint::new() must be {} bits long",
+            Self::BIT_LEN
+        );
+        Self { bits: bits.clone() }
+    }
+
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    /// Builds a value from its integer representation, `raw / 2^FRACTION`.
+    #[inline]
+    #[must_use] pub fn from_raw(raw: i128) -> Self {
+        assert!(
+            (Self::min_raw()..=Self::max_raw()).contains(&raw),
+            "Raw value {} is out of range for {} bits",
+            raw,
+            Self::BIT_LEN
+        );
+        let raw_bits = raw.bits();
+        Self::new(&raw_bits.slice((128 - Self::BIT_LEN) as usize..))
+    }
+
+    #[inline]
+    #[must_use] pub fn raw(&self) -> i128 {
+        let unsigned_raw = self
+            .bits
+            .iter()
+            .fold(0u128, |raw, bit| (raw << 1) | u128::from(bit.0));
+        if SIGNED && self.bits[0].0 {
+            (unsigned_raw | (u128::MAX << Self::BIT_LEN)) as i128
+        } else {
+            unsigned_raw as i128
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn min_value() -> Self {
+        Self::from_raw(Self::min_raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn max_value() -> Self {
+        Self::from_raw(Self::max_raw())
+    }
+
+    /// Converts with the given rounding, saturating values outside of the
+    /// representable range.  NaN converts to zero.
+    #[must_use] pub fn from_f64(value: f64, rounding_mode: RoundingMode) -> Self {
+        let negative = value.sign().0;
+        let (magnitude, shift) = match value.float_class() {
+            FloatClass::QuietNan | FloatClass::SignalingNan | FloatClass::Zero => (0, 0),
+            FloatClass::Infinite => (u128::MAX, 0),
+            FloatClass::Normal | FloatClass::Subnormal => {
+                let significand = if value.biased_exponent() == 0 {
+                    value.mantissa()
+                } else {
+                    value.mantissa() | (1 << f64::MANTISSA_BITS)
+                };
+                let exponent = value.unbiased_exponent() - f64::MANTISSA_BITS as i32
+                    + FRACTION as i32;
+                if exponent >= 0 {
+                    let magnitude = u128::from(significand)
+                        .checked_shl(exponent as u32)
+                        .filter(|magnitude| magnitude >> exponent == u128::from(significand))
+                        .unwrap_or(u128::MAX);
+                    (magnitude, 0)
+                } else {
+                    (u128::from(significand), -exponent as u32)
+                }
+            }
+        };
+        Self::saturating_from_magnitude(
+            negative,
+            round_magnitude(negative, magnitude, shift, rounding_mode),
+        )
+    }
+
+    #[inline]
+    #[must_use] pub fn to_f64(&self) -> f64 {
+        self.raw() as f64 * power_of_two(-(FRACTION as i32))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_add(&self, rhs: &Self) -> Self {
+        Self::wrapping_from_raw(self.raw() + rhs.raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn saturating_add(&self, rhs: &Self) -> Self {
+        Self::saturating_from_raw(self.raw() + rhs.raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+        Self::wrapping_from_raw(self.raw() - rhs.raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn saturating_sub(&self, rhs: &Self) -> Self {
+        Self::saturating_from_raw(self.raw() - rhs.raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_neg(&self) -> Self {
+        Self::wrapping_from_raw(-self.raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn saturating_neg(&self) -> Self {
+        Self::saturating_from_raw(-self.raw())
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_mul(&self, rhs: &Self, rounding_mode: RoundingMode) -> Self {
+        let (negative, magnitude) = self.product(rhs, rounding_mode);
+        Self::wrapping_from_magnitude(negative, magnitude)
+    }
+
+    #[inline]
+    #[must_use] pub fn saturating_mul(&self, rhs: &Self, rounding_mode: RoundingMode) -> Self {
+        let (negative, magnitude) = self.product(rhs, rounding_mode);
+        Self::saturating_from_magnitude(negative, magnitude)
+    }
+
+    /// Converts to another Q format, rounding away surplus fractional bits
+    /// and saturating values outside of the new range.
+    #[inline]
+    #[must_use] pub fn requantize<const NEW_INTEGER: u32, const NEW_FRACTION: u32, const NEW_SIGNED: bool>(
+        &self,
+        rounding_mode: RoundingMode,
+    ) -> FixedPoint<NEW_INTEGER, NEW_FRACTION, NEW_SIGNED> {
+        let (negative, magnitude) = self.rescaled_magnitude(NEW_FRACTION, rounding_mode);
+        FixedPoint::saturating_from_magnitude(negative, magnitude)
+    }
+
+    /// Converts to another Q format, rounding away surplus fractional bits
+    /// and discarding integer bits that do not fit.
+    #[inline]
+    #[must_use] pub fn wrapping_requantize<const NEW_INTEGER: u32, const NEW_FRACTION: u32, const NEW_SIGNED: bool>(
+        &self,
+        rounding_mode: RoundingMode,
+    ) -> FixedPoint<NEW_INTEGER, NEW_FRACTION, NEW_SIGNED> {
+        let (negative, magnitude) = self.rescaled_magnitude(NEW_FRACTION, rounding_mode);
+        FixedPoint::wrapping_from_magnitude(negative, magnitude)
+    }
+
+    #[inline]
+    fn min_raw() -> i128 {
+        if SIGNED {
+            -(1 << (Self::BIT_LEN - 1))
+        } else {
+            0
+        }
+    }
+
+    #[inline]
+    fn max_raw() -> i128 {
+        if SIGNED {
+            (1 << (Self::BIT_LEN - 1)) - 1
+        } else {
+            (1 << Self::BIT_LEN) - 1
+        }
+    }
+
+    #[inline]
+    fn product(&self, rhs: &Self, rounding_mode: RoundingMode) -> (bool, u128) {
+        let (lhs_raw, rhs_raw) = (self.raw(), rhs.raw());
+        let negative = (lhs_raw < 0) != (rhs_raw < 0);
+        let magnitude = lhs_raw.unsigned_abs() * rhs_raw.unsigned_abs();
+        (
+            negative,
+            round_magnitude(negative, magnitude, FRACTION, rounding_mode),
+        )
+    }
+
+    #[inline]
+    fn rescaled_magnitude(&self, new_fraction: u32, rounding_mode: RoundingMode) -> (bool, u128) {
+        let raw = self.raw();
+        let negative = raw < 0;
+        let magnitude = raw.unsigned_abs();
+        if new_fraction >= FRACTION {
+            (
+                negative,
+                magnitude.saturating_mul(1 << (new_fraction - FRACTION)),
+            )
+        } else {
+            (
+                negative,
+                round_magnitude(negative, magnitude, FRACTION - new_fraction, rounding_mode),
+            )
+        }
+    }
+
+    #[inline]
+    fn wrapping_from_raw(raw: i128) -> Self {
+        Self::wrapping_from_magnitude(raw < 0, raw.unsigned_abs())
+    }
+
+    #[inline]
+    fn saturating_from_raw(raw: i128) -> Self {
+        Self::saturating_from_magnitude(raw < 0, raw.unsigned_abs())
+    }
+
+    #[inline]
+    fn wrapping_from_magnitude(negative: bool, magnitude: u128) -> Self {
+        let twos_complement = if negative {
+            magnitude.wrapping_neg()
+        } else {
+            magnitude
+        };
+        let raw_bits = twos_complement.bits();
+        Self::new(&raw_bits.slice((128 - Self::BIT_LEN) as usize..))
+    }
+
+    #[inline]
+    fn saturating_from_magnitude(negative: bool, magnitude: u128) -> Self {
+        if negative {
+            if magnitude > Self::min_raw().unsigned_abs() {
+                Self::min_value()
+            } else {
+                Self::from_raw(-(magnitude as i128))
+            }
+        } else if magnitude > Self::max_raw() as u128 {
+            Self::max_value()
+        } else {
+            Self::from_raw(magnitude as i128)
+        }
+    }
+}
+
+/// Divides `magnitude` by `2^shift`, rounding the quotient of a value with the
+/// given sign as `rounding_mode` dictates.
+#[inline]
+fn round_magnitude(negative: bool, magnitude: u128, shift: u32, rounding_mode: RoundingMode) -> u128 {
+    if shift == 0 {
+        return magnitude
+    }
+    if shift > 128 {
+        return u128::from(rounding_mode.rounds_up(negative, false, Ordering::Less, magnitude != 0))
+    }
+    let (truncated, remainder) = if shift == 128 {
+        (0, magnitude)
+    } else {
+        (magnitude >> shift, magnitude & ((1 << shift) - 1))
+    };
+    let half = 1u128 << (shift - 1);
+    truncated
+        + u128::from(rounding_mode.rounds_up(
+            negative,
+            truncated & 1 == 1,
+            remainder.cmp(&half),
+            remainder != 0,
+        ))
+}
+
+impl<const INTEGER: u32, const FRACTION: u32, const SIGNED: bool> BitMan
+    for FixedPoint<INTEGER, FRACTION, SIGNED>
+{
+    #[inline]
+    fn bit_len(&self) -> usize {
+        Self::BIT_LEN as usize
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        self.bits[*index as usize]
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        self.bits.set(*index as usize, *bit);
+    }
+}
@@ -0,0 +1,144 @@
+use crate as bitman;
+use bitman::{BitMan, Bits, FixedPoint, RoundingMode, Q15, Q31};
+use proptest::prelude::*;
+
+const NEAREST: RoundingMode = RoundingMode::NearestTiesToEven;
+
+#[test]
+fn q15_half_has_expected_bit_pattern() {
+    let half = Q15::from_f64(0.5, NEAREST);
+    assert_eq!(*half.as_bits(), 0x4000u16.bits());
+    assert_eq!(half.raw(), 0x4000);
+    assert_eq!(half.to_f64(), 0.5);
+}
+
+#[test]
+fn q15_range_is_minus_one_to_just_below_one() {
+    assert_eq!(Q15::min_value().to_f64(), -1.0);
+    assert_eq!(Q15::max_value().to_f64(), 1.0 - 2f64.powi(-15));
+    assert_eq!(Q15::from_f64(1.0, NEAREST), Q15::max_value());
+    assert_eq!(Q15::from_f64(-3.0, NEAREST), Q15::min_value());
+    assert_eq!(Q15::from_f64(f64::INFINITY, NEAREST), Q15::max_value());
+    assert_eq!(Q15::from_f64(f64::NAN, NEAREST).raw(), 0);
+}
+
+#[test]
+fn negative_values_are_twos_complement() {
+    let minus_quarter = Q31::from_f64(-0.25, NEAREST);
+    assert_eq!(*minus_quarter.as_bits(), 0xE000_0000u32.bits());
+    assert_eq!(minus_quarter.raw(), -(1 << 29));
+}
+
+#[test]
+fn from_f64_honours_rounding_mode() {
+    type Q4_4 = FixedPoint<4, 4, true>;
+    let between = 1.0 / 32.0 + 1.0;
+    assert_eq!(Q4_4::from_f64(between, NEAREST).raw(), 16);
+    assert_eq!(
+        Q4_4::from_f64(between, RoundingMode::NearestTiesAwayFromZero).raw(),
+        17
+    );
+    assert_eq!(Q4_4::from_f64(-between, RoundingMode::TowardNegative).raw(), -17);
+    assert_eq!(Q4_4::from_f64(-between, RoundingMode::TowardPositive).raw(), -16);
+    assert_eq!(Q4_4::from_f64(-between, RoundingMode::TowardZero).raw(), -16);
+}
+
+#[test]
+fn add_and_sub_wrap_or_saturate() {
+    let big = Q15::from_f64(0.75, NEAREST);
+    assert_eq!(big.saturating_add(&big), Q15::max_value());
+    assert_eq!(big.wrapping_add(&big).to_f64(), -0.5);
+    let small = Q15::from_f64(-0.75, NEAREST);
+    assert_eq!(small.saturating_sub(&big), Q15::min_value());
+    assert_eq!(small.wrapping_sub(&big).to_f64(), 0.5);
+}
+
+#[test]
+fn negating_minimum_saturates_or_wraps() {
+    assert_eq!(Q15::min_value().saturating_neg(), Q15::max_value());
+    assert_eq!(Q15::min_value().wrapping_neg(), Q15::min_value());
+}
+
+#[test]
+fn multiplication_rounds_and_saturates() {
+    let half = Q15::from_f64(0.5, NEAREST);
+    let minus_half = Q15::from_f64(-0.5, NEAREST);
+    assert_eq!(half.saturating_mul(&minus_half, NEAREST).to_f64(), -0.25);
+    assert_eq!(
+        Q15::min_value().saturating_mul(&Q15::min_value(), NEAREST),
+        Q15::max_value()
+    );
+    assert_eq!(
+        Q15::min_value().wrapping_mul(&Q15::min_value(), NEAREST),
+        Q15::min_value()
+    );
+    let smallest = Q15::from_raw(1);
+    assert_eq!(smallest.saturating_mul(&half, NEAREST).raw(), 0);
+    assert_eq!(
+        smallest
+            .saturating_mul(&half, RoundingMode::NearestTiesAwayFromZero)
+            .raw(),
+        1
+    );
+}
+
+#[test]
+fn unsigned_fixed_point_has_no_negative_values() {
+    type UQ8_8 = FixedPoint<8, 8, false>;
+    assert_eq!(UQ8_8::max_value().to_f64(), 256.0 - 1.0 / 256.0);
+    assert_eq!(UQ8_8::from_f64(-1.0, NEAREST).raw(), 0);
+    assert_eq!(UQ8_8::from_raw(0xFFFF).as_bits().clone(), 0xFFFFu16.bits());
+}
+
+#[test]
+fn requantize_rounds_and_saturates() {
+    let value = Q31::from_f64(0.123_456_789, NEAREST);
+    let narrowed: Q15 = value.requantize(NEAREST);
+    assert_eq!(narrowed.raw(), (0.123_456_789f64 * 32768.0).round() as i128);
+    let widened: Q31 = narrowed.requantize(NEAREST);
+    assert_eq!(widened.raw(), narrowed.raw() << 16);
+    let wide = FixedPoint::<8, 8, true>::from_f64(3.5, NEAREST);
+    let saturated: Q15 = wide.requantize(NEAREST);
+    assert_eq!(saturated, Q15::max_value());
+    let wrapped: FixedPoint<2, 8, true> = wide.wrapping_requantize(NEAREST);
+    assert_eq!(wrapped.to_f64(), -0.5);
+}
+
+#[test]
+fn set_bit_changes_raw_value() {
+    let mut value = Q15::from_raw(0);
+    value.set_bit(&15, &bitman::Bit(true));
+    assert_eq!(value.raw(), 1);
+}
+
+#[test]
+#[should_panic]
+fn from_raw_out_of_range_panics() {
+    let _ = Q15::from_raw(1 << 15);
+}
+
+#[test]
+#[should_panic]
+fn new_with_wrong_length_panics() {
+    let _ = Q15::new(&Bits::new(&[]));
+}
+
+proptest! {
+    #[test]
+    fn raw_round_trips_through_f64(raw in -(1i128 << 31)..(1i128 << 31)) {
+        let value = Q31::from_raw(raw);
+        prop_assert_eq!(Q31::from_f64(value.to_f64(), NEAREST), value);
+    }
+
+    #[test]
+    fn saturating_add_matches_clamped_sum(lhs in any::<i16>(), rhs in any::<i16>()) {
+        let sum = Q15::from_raw(i128::from(lhs)).saturating_add(&Q15::from_raw(i128::from(rhs)));
+        prop_assert_eq!(sum.raw(), i128::from(lhs.saturating_add(rhs)));
+    }
+
+    #[test]
+    fn wrapping_add_matches_integer_wrapping(lhs in any::<i16>(), rhs in any::<i16>()) {
+        let sum = Q15::from_raw(i128::from(lhs)).wrapping_add(&Q15::from_raw(i128::from(rhs)));
+        prop_assert_eq!(sum.raw(), i128::from(lhs.wrapping_add(rhs)));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod floats_tests;
+
+/// The kind of value an IEEE-754 bit pattern encodes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum FloatClass {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    QuietNan,
+    SignalingNan,
+}
+
+/// Read and assemble the sign, exponent and mantissa fields of an IEEE-754
+/// binary floating point value.
+pub trait FloatParts: BitMan + Sized {
+    const EXPONENT_BITS: u32;
+    const MANTISSA_BITS: u32;
+    const EXPONENT_BIAS: i32;
+
+    fn sign(&self) -> Bit;
+
+    fn biased_exponent(&self) -> u32;
+
+    fn mantissa(&self) -> u64;
+
+    fn from_parts(sign: Bit, biased_exponent: u32, mantissa: u64) -> Self;
+
+    /// The exponent with the bias removed.  Zero and subnormal values report
+    /// the minimum normal exponent, which is the exponent they are scaled by.
+    #[inline]
+    fn unbiased_exponent(&self) -> i32 {
+        let biased_exponent = self.biased_exponent();
+        if biased_exponent == 0 {
+            1 - Self::EXPONENT_BIAS
+        } else {
+            biased_exponent as i32 - Self::EXPONENT_BIAS
+        }
+    }
+
+    #[inline]
+    fn exponent_bits(&self) -> Bits {
+        let bits = self.bits();
+        bits.slice(1..=Self::EXPONENT_BITS as usize)
+    }
+
+    #[inline]
+    fn mantissa_bits(&self) -> Bits {
+        let bits = self.bits();
+        bits.slice(1 + Self::EXPONENT_BITS as usize..)
+    }
+
+    #[inline]
+    fn from_bits_parts(sign: Bit, exponent: &Bits, mantissa: &Bits) -> Self {
+        assert_eq!(
+            exponent.len(),
+            Self::EXPONENT_BITS as usize,
+            "Exponent passed to from_bits_parts() must be {} bits long",
+            Self::EXPONENT_BITS
+        );
+        assert_eq!(
+            mantissa.len(),
+            Self::MANTISSA_BITS as usize,
+            "Mantissa passed to from_bits_parts() must be {} bits long",
+            Self::MANTISSA_BITS
+        );
+        let mut output_value = Self::from_parts(sign, 0, 0);
+        output_value.set_bits(1, exponent);
+        output_value.set_bits(1 + Self::EXPONENT_BITS, mantissa);
+        output_value
+    }
+
+    /// Builds a NaN carrying `payload` in the mantissa bits below the quiet bit.
+    #[inline]
+    fn nan_with_payload(sign: Bit, quiet: bool, payload: u64) -> Self {
+        let quiet_bit = 1u64 << (Self::MANTISSA_BITS - 1);
+        assert!(
+            payload < quiet_bit,
+            "Payload {} does not fit below the quiet bit of a NaN",
+            payload
+        );
+        assert!(
+            quiet || payload != 0,
+            "A signaling NaN needs a non-zero payload"
+        );
+        let mantissa = if quiet { quiet_bit | payload } else { payload };
+        Self::from_parts(sign, (1 << Self::EXPONENT_BITS) - 1, mantissa)
+    }
+
+    #[inline]
+    fn nan_payload(&self) -> Option<u64> {
+        match self.float_class() {
+            FloatClass::QuietNan | FloatClass::SignalingNan => {
+                Some(self.mantissa() & ((1u64 << (Self::MANTISSA_BITS - 1)) - 1))
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn float_class(&self) -> FloatClass {
+        let maximum_exponent = (1 << Self::EXPONENT_BITS) - 1;
+        let mantissa = self.mantissa();
+        match self.biased_exponent() {
+            0 if mantissa == 0 => FloatClass::Zero,
+            0 => FloatClass::Subnormal,
+            exponent if exponent == maximum_exponent => {
+                if mantissa == 0 {
+                    FloatClass::Infinite
+                } else if mantissa >> (Self::MANTISSA_BITS - 1) == 1 {
+                    FloatClass::QuietNan
+                } else {
+                    FloatClass::SignalingNan
+                }
+            }
+            _ => FloatClass::Normal,
+        }
+    }
+}
+
+macro_rules! impl_bitman_and_float_parts_for_floats {
+    ($($float_type:ty => $integer_type:ty, $exponent_bits:expr, $mantissa_bits:expr$(;)?)*) => {$(
+        impl BitMan for $float_type {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                self.to_bits().bit_len()
+            }
+
+            #[inline]
+            fn bit(&self, index: &u32) -> Bit {
+                self.to_bits().bit(index)
+            }
+
+            #[inline]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                let mut raw_value = self.to_bits();
+                raw_value.set_bit(index, bit);
+                *self = <$float_type>::from_bits(raw_value);
+            }
+        }
+
+        impl FloatParts for $float_type {
+            const EXPONENT_BITS: u32 = $exponent_bits;
+            const MANTISSA_BITS: u32 = $mantissa_bits;
+            const EXPONENT_BIAS: i32 = (1 << ($exponent_bits - 1)) - 1;
+
+            #[inline]
+            fn sign(&self) -> Bit {
+                Bit(self.is_sign_negative())
+            }
+
+            #[inline]
+            fn biased_exponent(&self) -> u32 {
+                ((self.to_bits() >> Self::MANTISSA_BITS) as u32) & ((1 << Self::EXPONENT_BITS) - 1)
+            }
+
+            #[inline]
+            fn mantissa(&self) -> u64 {
+                (self.to_bits() as u64) & ((1u64 << Self::MANTISSA_BITS) - 1)
+            }
+
+            #[inline]
+            fn from_parts(sign: Bit, biased_exponent: u32, mantissa: u64) -> Self {
+                assert!(
+                    biased_exponent < (1 << Self::EXPONENT_BITS),
+                    "Exponent {} is too large for {}",
+                    biased_exponent,
+                    stringify!($float_type)
+                );
+                assert!(
+                    mantissa < (1u64 << Self::MANTISSA_BITS),
+                    "Mantissa {} is too large for {}",
+                    mantissa,
+                    stringify!($float_type)
+                );
+                let raw_value = ((sign.0 as $integer_type) << ($exponent_bits + $mantissa_bits))
+                    | ((biased_exponent as $integer_type) << $mantissa_bits)
+                    | (mantissa as $integer_type);
+                <$float_type>::from_bits(raw_value)
+            }
+        }
+    )*}
+}
+
+impl_bitman_and_float_parts_for_floats!(
+    f32 => u32, 8, 23;
+    f64 => u64, 11, 52;
+);
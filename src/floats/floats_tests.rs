@@ -0,0 +1,91 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits, FloatClass, FloatParts};
+use proptest::prelude::*;
+
+#[test]
+fn bits_of_f32_match_bits_of_its_raw_value() {
+    assert_eq!(1.5f32.bits(), 1.5f32.to_bits().bits());
+    assert_eq!((-2.0f64).bits(), (-2.0f64).to_bits().bits());
+}
+
+#[test]
+fn set_bit_on_sign_flips_sign_of_f64() {
+    let mut value = 3.25f64;
+    value.set_bit(&0, &Bit(true));
+    assert_eq!(value, -3.25);
+}
+
+#[test]
+fn parts_of_one_point_five_f32() {
+    assert_eq!(1.5f32.sign(), Bit(false));
+    assert_eq!(1.5f32.biased_exponent(), 127);
+    assert_eq!(1.5f32.unbiased_exponent(), 0);
+    assert_eq!(1.5f32.mantissa(), 1 << 22);
+    assert_eq!(1.5f32.exponent_bits(), 127u8.bits());
+    assert_eq!(1.5f32.mantissa_bits().len(), 23);
+    assert_eq!(1.5f32.mantissa_bits()[0], Bit(true));
+}
+
+#[test]
+fn unbiased_exponent_of_subnormal_is_minimum_normal_exponent() {
+    assert_eq!(f64::from_bits(1).unbiased_exponent(), -1022);
+    assert_eq!(f32::MIN_POSITIVE.unbiased_exponent(), -126);
+}
+
+#[test]
+fn float_class_of_special_values() {
+    assert_eq!(0.0f32.float_class(), FloatClass::Zero);
+    assert_eq!((-0.0f64).float_class(), FloatClass::Zero);
+    assert_eq!(f32::from_bits(1).float_class(), FloatClass::Subnormal);
+    assert_eq!(1.0f64.float_class(), FloatClass::Normal);
+    assert_eq!(f32::NEG_INFINITY.float_class(), FloatClass::Infinite);
+    assert_eq!(f64::NAN.float_class(), FloatClass::QuietNan);
+    assert_eq!(f32::from_bits(0x7F80_0001).float_class(), FloatClass::SignalingNan);
+}
+
+#[test]
+fn nan_with_payload_keeps_its_payload() {
+    let quiet = f64::nan_with_payload(Bit(true), true, 0x1234);
+    assert_eq!(quiet.float_class(), FloatClass::QuietNan);
+    assert_eq!(quiet.sign(), Bit(true));
+    assert_eq!(quiet.nan_payload(), Some(0x1234));
+    let signaling = f32::nan_with_payload(Bit(false), false, 7);
+    assert_eq!(signaling.float_class(), FloatClass::SignalingNan);
+    assert_eq!(signaling.nan_payload(), Some(7));
+    assert_eq!(1.0f32.nan_payload(), None);
+}
+
+#[test]
+#[should_panic]
+fn signaling_nan_without_payload_panics() {
+    let _ = f32::nan_with_payload(Bit(false), false, 0);
+}
+
+#[test]
+fn from_bits_parts_assembles_negative_two() {
+    let exponent = 128u8.bits();
+    let mantissa = Bits::new(&[Bit(false); 23]);
+    assert_eq!(f32::from_bits_parts(Bit(true), &exponent, &mantissa), -2.0);
+}
+
+#[test]
+#[should_panic]
+fn from_bits_parts_with_short_exponent_panics() {
+    let _ = f64::from_bits_parts(Bit(false), &0u8.bits(), &Bits::new(&[Bit(false); 52]));
+}
+
+proptest! {
+    #[test]
+    fn from_parts_round_trips_any_f64(raw_value in any::<u64>()) {
+        let value = f64::from_bits(raw_value);
+        let rebuilt = f64::from_parts(value.sign(), value.biased_exponent(), value.mantissa());
+        prop_assert_eq!(rebuilt.to_bits(), raw_value);
+    }
+
+    #[test]
+    fn from_bits_parts_round_trips_any_f32(raw_value in any::<u32>()) {
+        let value = f32::from_bits(raw_value);
+        let rebuilt = f32::from_bits_parts(value.sign(), &value.exponent_bits(), &value.mantissa_bits());
+        prop_assert_eq!(rebuilt.to_bits(), raw_value);
+    }
+}
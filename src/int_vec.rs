@@ -0,0 +1,258 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::mem::size_of;
+
+use crate::scanning::{read_field, write_field};
+use crate::Bits;
+
+#[cfg(test)]
+mod int_vec_tests;
+
+/// A vector of unsigned integers that are each `width` bits wide, packed
+/// back to back into 64 bit words.
+///
+/// Value `i` occupies bits `i * width` to `(i + 1) * width` of the words,
+/// bit `j` living at bit `j % 64` of word `j / 64`, so a value is read with
+/// a shift and a mask and may straddle two words.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct IntVec {
+    words: Vec<u64>,
+    len: usize,
+    width: u32,
+}
+
+impl IntVec {
+    #[inline]
+    #[must_use] pub fn new(width: u32) -> Self {
+        Self::with_capacity(width, 0)
+    }
+
+    #[inline]
+    #[must_use] pub fn with_capacity(width: u32, capacity: usize) -> Self {
+        assert!(
+            (1..=64).contains(&width),
+            "IntVec supports widths of 1 to 64 bits, not {}",
+            width
+        );
+        Self {
+            words: Vec::with_capacity((capacity * width as usize + 63) / 64),
+            len: 0,
+            width,
+        }
+    }
+
+    /// Packs `values` using `width` bits for each of them.
+    #[must_use] pub fn from_values(values: &[u64], width: u32) -> Self {
+        let mut int_vec = Self::with_capacity(width, values.len());
+        for &value in values {
+            int_vec.push(value);
+        }
+        int_vec
+    }
+
+    /// Packs bits holding values that are each `width` bits wide, most
+    /// significant bit first.
+    #[must_use] pub fn from_bits(bits: &Bits, width: u32) -> Self {
+        assert_eq!(
+            bits.len() % width as usize,
+            0,
+            "Bits passed to IntVec::from_bits() must hold whole {} bit values",
+            width
+        );
+        let mut int_vec = Self::with_capacity(width, bits.len() / width as usize);
+        for offset in (0..bits.len()).step_by(width as usize) {
+            int_vec.push(bits.field(offset, width));
+        }
+        int_vec
+    }
+
+    /// The smallest width that holds every value in `values`.
+    #[inline]
+    #[must_use] pub fn min_width(values: &[u64]) -> u32 {
+        let largest = values.iter().copied().max().unwrap_or(0);
+        (64 - largest.leading_zeros()).max(1)
+    }
+
+    #[inline]
+    #[must_use] pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The packed words.  Bits past the last value are zero.
+    #[inline]
+    #[must_use] pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Bytes used by the packed words.
+    #[inline]
+    #[must_use] pub fn size_in_bytes(&self) -> usize {
+        self.words.len() * size_of::<u64>()
+    }
+
+    /// The values back to back, each most significant bit first.
+    #[must_use] pub fn to_bits(&self) -> Bits {
+        let mut bits = Bits::zeroed(self.len * self.width as usize);
+        for (index, value) in self.iter().enumerate() {
+            bits.set_field(index * self.width as usize, self.width, value);
+        }
+        bits
+    }
+
+    #[inline]
+    #[must_use] pub fn get(&self, index: usize) -> Option<u64> {
+        if index >= self.len {
+            return None
+        }
+        Some(read_field(&self.words, index * self.width as usize, self.width))
+    }
+
+    #[inline]
+    pub fn set(&mut self, index: usize, value: u64) {
+        assert!(
+            index < self.len,
+            "Index {} passed to set() is beyond the end of {} values",
+            index,
+            self.len
+        );
+        self.check_fits(value);
+        write_field(&mut self.words, index * self.width as usize, self.width, value);
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: u64) {
+        self.check_fits(value);
+        self.len += 1;
+        self.words.resize((self.len * self.width as usize + 63) / 64, 0);
+        write_field(&mut self.words, (self.len - 1) * self.width as usize, self.width, value);
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<u64> {
+        let value = self.get(self.len.checked_sub(1)?);
+        self.len -= 1;
+        write_field(&mut self.words, self.len * self.width as usize, self.width, 0);
+        self.words.truncate((self.len * self.width as usize + 63) / 64);
+        value
+    }
+
+    /// Repacks every value with `new_width` bits.
+    pub fn set_width(&mut self, new_width: u32) {
+        let values = self.to_vec();
+        assert!(
+            Self::min_width(&values) <= new_width,
+            "Values do not fit in {} bits",
+            new_width
+        );
+        *self = Self::from_values(&values, new_width);
+    }
+
+    /// Repacks every value with the smallest width that holds them all.
+    #[inline]
+    pub fn shrink_width(&mut self) {
+        let values = self.to_vec();
+        *self = Self::from_values(&values, Self::min_width(&values));
+    }
+
+    #[inline]
+    #[must_use] pub fn to_vec(&self) -> Vec<u64> {
+        self.iter().collect()
+    }
+
+    #[inline]
+    #[must_use] pub fn iter(&self) -> IntVecIter<'_> {
+        IntVecIter {
+            int_vec: self,
+            front: 0,
+            back: self.len,
+        }
+    }
+
+    #[inline]
+    fn check_fits(&self, value: u64) {
+        assert!(
+            self.width == 64 || value >> self.width == 0,
+            "Value {} does not fit in {} bits",
+            value,
+            self.width
+        );
+    }
+}
+
+impl From<&IntVec> for Vec<u64> {
+    #[inline]
+    fn from(int_vec: &IntVec) -> Vec<u64> {
+        int_vec.to_vec()
+    }
+}
+
+impl FromIterator<u64> for IntVec {
+    /// Packs the values with the smallest width that holds them all.
+    #[inline]
+    fn from_iter<Values: IntoIterator<Item = u64>>(values: Values) -> Self {
+        let values: Vec<u64> = values.into_iter().collect();
+        Self::from_values(&values, Self::min_width(&values))
+    }
+}
+
+impl<'a> IntoIterator for &'a IntVec {
+    type Item = u64;
+    type IntoIter = IntVecIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the values of an `IntVec`.
+#[derive(Debug, Clone)]
+pub struct IntVecIter<'a> {
+    int_vec: &'a IntVec,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for IntVecIter<'_> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        if self.front == self.back {
+            return None
+        }
+        self.front += 1;
+        self.int_vec.get(self.front - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.back - self.front, Some(self.back - self.front))
+    }
+}
+
+impl DoubleEndedIterator for IntVecIter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<u64> {
+        if self.front == self.back {
+            return None
+        }
+        self.back -= 1;
+        self.int_vec.get(self.back)
+    }
+}
+
+impl ExactSizeIterator for IntVecIter<'_> {}
+
+impl FusedIterator for IntVecIter<'_> {}
@@ -0,0 +1,106 @@
+use crate as bitman;
+use bitman::{Bits, IntVec};
+use proptest::prelude::*;
+
+#[test]
+fn values_are_packed_back_to_back() {
+    let mut int_vec = IntVec::new(5);
+    int_vec.push(0b10101);
+    int_vec.push(0b00011);
+    int_vec.push(0b11111);
+    assert_eq!(int_vec.len(), 3);
+    assert_eq!(int_vec.as_words(), &[0b11111_00011_10101]);
+    let mut expected = Bits::from_be_bytes(&[0b1010_1000, 0b1111_1110]);
+    expected.truncate(15);
+    assert_eq!(int_vec.to_bits(), expected);
+    assert_eq!(IntVec::from_bits(&expected, 5), int_vec);
+}
+
+#[test]
+fn values_straddle_words() {
+    let values: Vec<u64> = (0..40).map(|index| (index * 37) % 128).collect();
+    let mut int_vec = IntVec::from_values(&values, 7);
+    assert_eq!(int_vec.as_words().len(), 5);
+    assert_eq!(int_vec.size_in_bytes(), 40);
+    assert_eq!(int_vec.get(9), Some(values[9]));
+    int_vec.set(9, 127);
+    assert_eq!(int_vec.get(8), Some(values[8]));
+    assert_eq!(int_vec.get(9), Some(127));
+    assert_eq!(int_vec.get(10), Some(values[10]));
+    while int_vec.len() > 9 {
+        int_vec.pop();
+    }
+    assert_eq!(int_vec, IntVec::from_values(&values[..9], 7));
+}
+
+#[test]
+fn get_set_and_pop() {
+    let mut int_vec = IntVec::from_values(&[1, 2, 3, 2047], 11);
+    assert_eq!(int_vec.get(3), Some(2047));
+    assert_eq!(int_vec.get(4), None);
+    int_vec.set(1, 1000);
+    assert_eq!(int_vec.to_vec(), vec![1, 1000, 3, 2047]);
+    assert_eq!(int_vec.pop(), Some(2047));
+    assert_eq!(int_vec.len(), 3);
+    assert_eq!(IntVec::new(3).pop(), None);
+}
+
+#[test]
+#[should_panic]
+fn push_of_value_too_wide_panics() {
+    IntVec::new(5).push(32);
+}
+
+#[test]
+#[should_panic]
+fn set_beyond_end_panics() {
+    IntVec::new(5).set(0, 1);
+}
+
+#[test]
+fn width_changes_repack_values() {
+    let mut int_vec = IntVec::from_values(&[3, 9, 17], 16);
+    int_vec.shrink_width();
+    assert_eq!(int_vec.width(), 5);
+    assert_eq!(int_vec.to_bits().len(), 15);
+    int_vec.set_width(40);
+    assert_eq!(int_vec.to_vec(), vec![3, 9, 17]);
+    assert_eq!(int_vec.to_bits().len(), 120);
+}
+
+#[test]
+#[should_panic]
+fn narrowing_below_largest_value_panics() {
+    IntVec::from_values(&[3, 9, 17], 8).set_width(4);
+}
+
+#[test]
+fn collect_uses_minimal_width() {
+    let int_vec: IntVec = vec![0, 0, 0].into_iter().collect();
+    assert_eq!(int_vec.width(), 1);
+    let int_vec: IntVec = vec![u64::MAX, 1].into_iter().collect();
+    assert_eq!(int_vec.width(), 64);
+    assert_eq!(Vec::<u64>::from(&int_vec), vec![u64::MAX, 1]);
+}
+
+#[test]
+fn iterates_from_both_ends() {
+    let int_vec = IntVec::from_values(&[4, 5, 6, 7], 3);
+    assert_eq!(int_vec.iter().len(), 4);
+    assert_eq!(int_vec.iter().rev().collect::<Vec<u64>>(), vec![7, 6, 5, 4]);
+    assert_eq!((&int_vec).into_iter().sum::<u64>(), 22);
+}
+
+proptest! {
+    #[test]
+    fn round_trips_any_width(width in 1u32..=64, raw_values in proptest::collection::vec(any::<u64>(), 0..50)) {
+        let values: Vec<u64> = raw_values
+            .into_iter()
+            .map(|value| if width == 64 { value } else { value & ((1 << width) - 1) })
+            .collect();
+        let int_vec = IntVec::from_values(&values, width);
+        prop_assert_eq!(int_vec.len(), values.len());
+        prop_assert_eq!(IntVec::from_bits(&int_vec.to_bits(), width), int_vec.clone());
+        prop_assert_eq!(int_vec.to_vec(), values);
+    }
+}
@@ -0,0 +1,281 @@
+use core::fmt::{self, Display};
+
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod integers_tests;
+
+/// An unsigned integer exactly `BITS` bits wide, for `BITS` from 1 to 127.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+pub struct UInt<const BITS: u32> {
+    value: u128,
+}
+
+/// A two's complement signed integer exactly `BITS` bits wide, for `BITS`
+/// from 1 to 127.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+pub struct IInt<const BITS: u32> {
+    value: i128,
+}
+
+impl<const BITS: u32> UInt<BITS> {
+    pub const MIN: Self = Self { value: 0 };
+    pub const MAX: Self = Self {
+        value: (1 << BITS) - 1,
+    };
+
+    #[inline]
+    #[must_use] pub fn new(value: u128) -> Self {
+        Self::checked_new(value).unwrap_or_else(|| {
+            panic!("Value {} does not fit in a {} bit unsigned integer", value, BITS)
+        })
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_new(value: u128) -> Option<Self> {
+        assert!((1..=127).contains(&BITS), "UInt supports 1 to 127 bits");
+        if value <= Self::MAX.value {
+            Some(Self { value })
+        } else {
+            None
+        }
+    }
+
+    /// Keeps only the least significant `BITS` bits of `value`.
+    #[inline]
+    #[must_use] pub fn wrapping_new(value: u128) -> Self {
+        assert!((1..=127).contains(&BITS), "UInt supports 1 to 127 bits");
+        Self {
+            value: value & Self::MAX.value,
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_add(&self, rhs: Self) -> Self {
+        Self::wrapping_new(self.value.wrapping_add(rhs.value))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_sub(&self, rhs: Self) -> Self {
+        Self::wrapping_new(self.value.wrapping_sub(rhs.value))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_mul(&self, rhs: Self) -> Self {
+        Self::wrapping_new(self.value.wrapping_mul(rhs.value))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_neg(&self) -> Self {
+        Self::wrapping_new(self.value.wrapping_neg())
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_add(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_sub(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_mul(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_div(rhs.value)?)
+    }
+}
+
+impl<const BITS: u32> IInt<BITS> {
+    pub const MIN: Self = Self {
+        value: -(1 << (BITS - 1)),
+    };
+    pub const MAX: Self = Self {
+        value: (1 << (BITS - 1)) - 1,
+    };
+
+    #[inline]
+    #[must_use] pub fn new(value: i128) -> Self {
+        Self::checked_new(value).unwrap_or_else(|| {
+            panic!("Value {} does not fit in a {} bit signed integer", value, BITS)
+        })
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_new(value: i128) -> Option<Self> {
+        assert!((1..=127).contains(&BITS), "IInt supports 1 to 127 bits");
+        if (Self::MIN.value..=Self::MAX.value).contains(&value) {
+            Some(Self { value })
+        } else {
+            None
+        }
+    }
+
+    /// Keeps only the least significant `BITS` bits of `value`, sign
+    /// extending from the new top bit.
+    #[inline]
+    #[must_use] pub fn wrapping_new(value: i128) -> Self {
+        assert!((1..=127).contains(&BITS), "IInt supports 1 to 127 bits");
+        Self {
+            value: (value << (128 - BITS)) >> (128 - BITS),
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_add(&self, rhs: Self) -> Self {
+        Self::wrapping_new(self.value.wrapping_add(rhs.value))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_sub(&self, rhs: Self) -> Self {
+        Self::wrapping_new(self.value.wrapping_sub(rhs.value))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_mul(&self, rhs: Self) -> Self {
+        Self::wrapping_new(self.value.wrapping_mul(rhs.value))
+    }
+
+    #[inline]
+    #[must_use] pub fn wrapping_neg(&self) -> Self {
+        Self::wrapping_new(self.value.wrapping_neg())
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_add(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_sub(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_mul(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_mul(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_div(&self, rhs: Self) -> Option<Self> {
+        Self::checked_new(self.value.checked_div(rhs.value)?)
+    }
+
+    #[inline]
+    #[must_use] pub fn checked_neg(&self) -> Option<Self> {
+        Self::checked_new(-self.value)
+    }
+
+    /// The `BITS` bit two's complement pattern of this value.
+    #[inline]
+    fn raw(&self) -> u128 {
+        (self.value as u128) & ((1 << BITS) - 1)
+    }
+}
+
+impl<const BITS: u32> BitMan for UInt<BITS> {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        BITS as usize
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        assert!(*index < BITS, "Index out of range in call to bit()");
+        self.value.bit(&(128 - BITS + index))
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        assert!(*index < BITS, "Index out of range in call to set_bit()");
+        self.value.set_bit(&(128 - BITS + index), bit);
+    }
+}
+
+impl<const BITS: u32> BitMan for IInt<BITS> {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        BITS as usize
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        assert!(*index < BITS, "Index out of range in call to bit()");
+        self.raw().bit(&(128 - BITS + index))
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        assert!(*index < BITS, "Index out of range in call to set_bit()");
+        let mut raw = self.raw();
+        raw.set_bit(&(128 - BITS + index), bit);
+        *self = Self::wrapping_new(raw as i128);
+    }
+}
+
+impl<const BITS: u32> From<&Bits> for UInt<BITS> {
+    /// Takes the last (least significant) `BITS` bits.
+    #[inline]
+    fn from(bits_to_convert: &Bits) -> Self {
+        Self::wrapping_new(
+            bits_to_convert
+                .iter()
+                .fold(0u128, |value, bit| (value << 1) | u128::from(bit.0)),
+        )
+    }
+}
+
+impl<const BITS: u32> From<&Bits> for IInt<BITS> {
+    /// Takes the last (least significant) `BITS` bits.
+    #[inline]
+    fn from(bits_to_convert: &Bits) -> Self {
+        Self::wrapping_new(
+            bits_to_convert
+                .iter()
+                .fold(0u128, |value, bit| (value << 1) | u128::from(bit.0)) as i128,
+        )
+    }
+}
+
+impl<const BITS: u32> From<UInt<BITS>> for u128 {
+    #[inline]
+    fn from(value: UInt<BITS>) -> u128 {
+        value.value
+    }
+}
+
+impl<const BITS: u32> From<IInt<BITS>> for i128 {
+    #[inline]
+    fn from(value: IInt<BITS>) -> i128 {
+        value.value
+    }
+}
+
+impl<const BITS: u32> Display for UInt<BITS> {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.value)
+    }
+}
+
+impl<const BITS: u32> Display for IInt<BITS> {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.value)
+    }
+}
@@ -0,0 +1,110 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits, IInt, UInt};
+use proptest::prelude::*;
+
+#[test]
+fn uint_range_is_checked() {
+    assert_eq!(UInt::<3>::MAX.value(), 7);
+    assert_eq!(UInt::<3>::checked_new(7), Some(UInt::<3>::new(7)));
+    assert_eq!(UInt::<3>::checked_new(8), None);
+    assert_eq!(UInt::<3>::wrapping_new(9).value(), 1);
+}
+
+#[test]
+#[should_panic]
+fn uint_new_out_of_range_panics() {
+    let _ = UInt::<5>::new(32);
+}
+
+#[test]
+fn iint_range_is_checked() {
+    assert_eq!(IInt::<5>::MIN.value(), -16);
+    assert_eq!(IInt::<5>::MAX.value(), 15);
+    assert_eq!(IInt::<5>::checked_new(-17), None);
+    assert_eq!(IInt::<5>::wrapping_new(16).value(), -16);
+    assert_eq!(IInt::<1>::MIN.value(), -1);
+    assert_eq!(IInt::<1>::MAX.value(), 0);
+}
+
+#[test]
+fn uint_arithmetic_wraps_or_checks() {
+    let seven = UInt::<3>::new(7);
+    let one = UInt::<3>::new(1);
+    assert_eq!(seven.wrapping_add(one).value(), 0);
+    assert_eq!(seven.checked_add(one), None);
+    assert_eq!(UInt::<3>::MIN.wrapping_sub(one), seven);
+    assert_eq!(one.checked_sub(seven), None);
+    assert_eq!(seven.wrapping_mul(seven).value(), 1);
+    assert_eq!(seven.checked_div(UInt::<3>::new(2)).map(|value| value.value()), Some(3));
+    assert_eq!(seven.checked_div(UInt::<3>::MIN), None);
+    assert_eq!(one.wrapping_neg(), seven);
+}
+
+#[test]
+fn iint_arithmetic_wraps_or_checks() {
+    let max = IInt::<12>::MAX;
+    let one = IInt::<12>::new(1);
+    assert_eq!(max.wrapping_add(one), IInt::<12>::MIN);
+    assert_eq!(max.checked_add(one), None);
+    assert_eq!(IInt::<12>::MIN.checked_neg(), None);
+    assert_eq!(IInt::<12>::MIN.wrapping_neg(), IInt::<12>::MIN);
+    assert_eq!(IInt::<12>::MIN.checked_div(IInt::<12>::new(-1)), None);
+    assert_eq!(IInt::<12>::new(-3).checked_mul(IInt::<12>::new(5)).map(i128::from), Some(-15));
+}
+
+#[test]
+fn bits_have_exactly_the_declared_width() {
+    assert_eq!(UInt::<5>::new(0b10110).bits(), 0b10110u8.bits().slice(3..));
+    assert_eq!(IInt::<4>::new(-2).bits(), 0b1110u8.bits().slice(4..));
+    assert_eq!(UInt::<24>::MAX.bit_len(), 24);
+}
+
+#[test]
+fn set_bit_on_iint_updates_sign() {
+    let mut value = IInt::<4>::new(3);
+    value.set_bit(&0, &Bit(true));
+    assert_eq!(value.value(), -5);
+}
+
+#[test]
+#[should_panic]
+fn bit_beyond_width_panics() {
+    let _ = UInt::<3>::new(0).bit(&3);
+}
+
+#[test]
+fn from_bits_takes_least_significant_bits() {
+    assert_eq!(UInt::<4>::from(&0xABu8.bits()).value(), 0xB);
+    assert_eq!(IInt::<4>::from(&0xABu8.bits()).value(), -5);
+    assert_eq!(UInt::<12>::from(&0xABu8.bits()).value(), 0xAB);
+}
+
+#[test]
+fn packed_header_round_trips_through_tuple() {
+    let header = (UInt::<3>::new(5), UInt::<5>::new(17), 0xC3u8);
+    let bits = header.bits();
+    assert_eq!(bits, Bits::from_be_bytes(&[0b1011_0001, 0xC3]));
+    let mut copy = (UInt::<3>::MIN, UInt::<5>::MIN, 0u8);
+    copy.set_bits(0, &bits);
+    assert_eq!(copy, header);
+}
+
+proptest! {
+    #[test]
+    fn uint_round_trips_through_bits(value in 0u128..(1 << 100)) {
+        let uint = UInt::<100>::new(value);
+        prop_assert_eq!(UInt::<100>::from(&uint.bits()), uint);
+    }
+
+    #[test]
+    fn iint_round_trips_through_bits(value in -(1i128 << 22)..(1i128 << 22)) {
+        let iint = IInt::<23>::new(value);
+        prop_assert_eq!(IInt::<23>::from(&iint.bits()), iint);
+    }
+
+    #[test]
+    fn iint_8_wrapping_add_matches_i8(lhs in any::<i8>(), rhs in any::<i8>()) {
+        let sum = IInt::<8>::new(i128::from(lhs)).wrapping_add(IInt::<8>::new(i128::from(rhs)));
+        prop_assert_eq!(sum.value(), i128::from(lhs.wrapping_add(rhs)));
+    }
+}
@@ -0,0 +1,611 @@
+//! Bulk kernels over the packed words behind `Bits`, with SSE2 and AVX2
+//! versions on `x86_64` and portable word-wise versions everywhere else.
+//!
+//! Every kernel produces identical output on every `Level`.  Kernels see
+//! whole words only; `Bits` masks the padding of its last word itself.
+
+#[cfg(all(target_arch = "x86_64", feature = "std"))]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(test)]
+mod kernels_tests;
+
+/// Instruction set a kernel runs with.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub(crate) enum Level {
+    #[cfg_attr(target_arch = "x86_64", allow(dead_code))]
+    Portable,
+    Sse2,
+    Avx2,
+}
+
+impl Level {
+    /// The best level this CPU supports.  Detected once at run time with
+    /// the `std` feature, otherwise fixed by the compile-time target
+    /// features.
+    #[inline]
+    pub(crate) fn detected() -> Self {
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
+        let level = {
+            static DETECTED: AtomicU8 = AtomicU8::new(0);
+            match DETECTED.load(Ordering::Relaxed) {
+                1 => return Self::Sse2,
+                2 => return Self::Avx2,
+                _ => {}
+            }
+            let level = if std::is_x86_feature_detected!("avx2") {
+                Self::Avx2
+            } else {
+                Self::Sse2
+            };
+            DETECTED.store(if level == Self::Avx2 { 2 } else { 1 }, Ordering::Relaxed);
+            level
+        };
+        #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+        let level = if cfg!(target_feature = "avx2") {
+            Self::Avx2
+        } else {
+            Self::Sse2
+        };
+        #[cfg(not(target_arch = "x86_64"))]
+        let level = Self::Portable;
+        level
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub(crate) enum BinaryOp {
+    And,
+    Or,
+    Xor,
+}
+
+/// `out[i] = lhs[i] op rhs[i]`.  All three slices must be the same length.
+#[inline]
+pub(crate) fn binary(op: BinaryOp, lhs: &[u64], rhs: &[u64], out: &mut [u64]) {
+    binary_with(Level::detected(), op, lhs, rhs, out);
+}
+
+/// `lhs[i] = lhs[i] op rhs[i]`.  Both slices must be the same length.
+#[inline]
+pub(crate) fn binary_assign(op: BinaryOp, lhs: &mut [u64], rhs: &[u64]) {
+    binary_assign_with(Level::detected(), op, lhs, rhs);
+}
+
+/// `words[i] = !words[i]`.
+#[inline]
+pub(crate) fn not_assign(words: &mut [u64]) {
+    not_assign_with(Level::detected(), words);
+}
+
+#[inline]
+pub(crate) fn count_ones(words: &[u64]) -> usize {
+    count_ones_with(Level::detected(), words)
+}
+
+/// Index of the first word that is not `skip`.
+#[inline]
+pub(crate) fn find_first(words: &[u64], skip: u64) -> Option<usize> {
+    find_first_with(Level::detected(), words, skip)
+}
+
+/// Index of the last word that is not `skip`.
+#[inline]
+pub(crate) fn find_last(words: &[u64], skip: u64) -> Option<usize> {
+    find_last_with(Level::detected(), words, skip)
+}
+
+#[inline]
+pub(crate) fn equal(lhs: &[u64], rhs: &[u64]) -> bool {
+    lhs.len() == rhs.len() && equal_with(Level::detected(), lhs, rhs)
+}
+
+/// Writes eight bytes per word, byte `j` holding bits `8 * j` to
+/// `8 * j + 7` with the first most significant.  `out` must hold exactly
+/// eight bytes per word.
+#[inline]
+pub(crate) fn pack_be(words: &[u64], out: &mut [u8]) {
+    pack_be_with(Level::detected(), words, out);
+}
+
+/// The inverse of `pack_be()`.  `bytes` must hold exactly eight bytes per
+/// word of `out`.
+#[inline]
+pub(crate) fn unpack_be(bytes: &[u8], out: &mut [u64]) {
+    unpack_be_with(Level::detected(), bytes, out);
+}
+
+pub(crate) fn binary_with(level: Level, op: BinaryOp, lhs: &[u64], rhs: &[u64], out: &mut [u64]) {
+    assert!(lhs.len() == out.len() && rhs.len() == out.len());
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::binary_avx2(op, lhs, rhs, out) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::binary_sse2(op, lhs, rhs, out),
+        _ => portable::binary(op, lhs, rhs, out),
+    }
+}
+
+pub(crate) fn binary_assign_with(level: Level, op: BinaryOp, lhs: &mut [u64], rhs: &[u64]) {
+    assert_eq!(lhs.len(), rhs.len());
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::binary_assign_avx2(op, lhs, rhs) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::binary_assign_sse2(op, lhs, rhs),
+        _ => portable::binary_assign(op, lhs, rhs),
+    }
+}
+
+pub(crate) fn not_assign_with(level: Level, words: &mut [u64]) {
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::not_assign_avx2(words) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::not_assign_sse2(words),
+        _ => portable::not_assign(words),
+    }
+}
+
+pub(crate) fn count_ones_with(level: Level, words: &[u64]) -> usize {
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::count_ones_avx2(words) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::count_ones_sse2(words),
+        _ => portable::count_ones(words),
+    }
+}
+
+pub(crate) fn find_first_with(level: Level, words: &[u64], skip: u64) -> Option<usize> {
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::find_first_avx2(words, skip) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::find_first_sse2(words, skip),
+        _ => portable::find_first(words, skip),
+    }
+}
+
+pub(crate) fn find_last_with(level: Level, words: &[u64], skip: u64) -> Option<usize> {
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::find_last_avx2(words, skip) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::find_last_sse2(words, skip),
+        _ => portable::find_last(words, skip),
+    }
+}
+
+pub(crate) fn equal_with(level: Level, lhs: &[u64], rhs: &[u64]) -> bool {
+    assert_eq!(lhs.len(), rhs.len());
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::equal_avx2(lhs, rhs) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::equal_sse2(lhs, rhs),
+        _ => lhs == rhs,
+    }
+}
+
+pub(crate) fn pack_be_with(level: Level, words: &[u64], out: &mut [u8]) {
+    assert_eq!(words.len() * 8, out.len());
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::reverse_byte_bits_avx2(x86::word_bytes(words), out) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::reverse_byte_bits_sse2(x86::word_bytes(words), out),
+        _ => portable::pack_be(words, out),
+    }
+}
+
+pub(crate) fn unpack_be_with(level: Level, bytes: &[u8], out: &mut [u64]) {
+    assert_eq!(bytes.len(), out.len() * 8);
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: Level::Avx2 is only used once AVX2 support is known.
+        Level::Avx2 => unsafe { x86::reverse_byte_bits_avx2(bytes, x86::word_bytes_mut(out)) },
+        #[cfg(target_arch = "x86_64")]
+        Level::Sse2 => x86::reverse_byte_bits_sse2(bytes, x86::word_bytes_mut(out)),
+        _ => portable::unpack_be(bytes, out),
+    }
+}
+
+/// One word at a time in general purpose registers.
+mod portable {
+    use super::BinaryOp;
+
+    #[inline]
+    fn apply(op: BinaryOp, lhs: u64, rhs: u64) -> u64 {
+        match op {
+            BinaryOp::And => lhs & rhs,
+            BinaryOp::Or => lhs | rhs,
+            BinaryOp::Xor => lhs ^ rhs,
+        }
+    }
+
+    pub(super) fn binary(op: BinaryOp, lhs: &[u64], rhs: &[u64], out: &mut [u64]) {
+        for ((out, lhs), rhs) in out.iter_mut().zip(lhs).zip(rhs) {
+            *out = apply(op, *lhs, *rhs);
+        }
+    }
+
+    pub(super) fn binary_assign(op: BinaryOp, lhs: &mut [u64], rhs: &[u64]) {
+        for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+            *lhs = apply(op, *lhs, *rhs);
+        }
+    }
+
+    pub(super) fn not_assign(words: &mut [u64]) {
+        for word in words {
+            *word = !*word;
+        }
+    }
+
+    pub(super) fn count_ones(words: &[u64]) -> usize {
+        words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub(super) fn find_first(words: &[u64], skip: u64) -> Option<usize> {
+        words.iter().position(|&word| word != skip)
+    }
+
+    pub(super) fn find_last(words: &[u64], skip: u64) -> Option<usize> {
+        words.iter().rposition(|&word| word != skip)
+    }
+
+    pub(super) fn pack_be(words: &[u64], out: &mut [u8]) {
+        for (bytes, word) in out.chunks_exact_mut(8).zip(words) {
+            bytes.copy_from_slice(&word.reverse_bits().to_be_bytes());
+        }
+    }
+
+    pub(super) fn unpack_be(bytes: &[u8], out: &mut [u64]) {
+        for (word, bytes) in out.iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut buffer = [0u8; 8];
+            buffer.copy_from_slice(bytes);
+            *word = u64::from_be_bytes(buffer).reverse_bits();
+        }
+    }
+}
+
+/// Two words at a time with SSE2, which every `x86_64` CPU has, or four at
+/// a time with AVX2.  Leftover words go through the portable kernels.
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::{portable, BinaryOp};
+    use core::arch::x86_64::*;
+
+    const SSE2_LANES: usize = 2;
+    const AVX2_LANES: usize = 4;
+
+    #[inline]
+    fn split(len: usize, lanes: usize) -> usize {
+        len / lanes * lanes
+    }
+
+    /// The words as their little endian bytes, which is how `x86_64` keeps
+    /// them in memory.
+    #[inline]
+    pub(super) fn word_bytes(words: &[u64]) -> &[u8] {
+        // SAFETY: u8 has no alignment or validity requirements and the
+        // slice covers exactly the memory of `words`.
+        unsafe { core::slice::from_raw_parts(words.as_ptr().cast(), words.len() * 8) }
+    }
+
+    #[inline]
+    pub(super) fn word_bytes_mut(words: &mut [u64]) -> &mut [u8] {
+        // SAFETY: as in word_bytes(); every byte pattern is a valid u64.
+        unsafe { core::slice::from_raw_parts_mut(words.as_mut_ptr().cast(), words.len() * 8) }
+    }
+
+    pub(super) fn binary_sse2(op: BinaryOp, lhs: &[u64], rhs: &[u64], out: &mut [u64]) {
+        let end = split(out.len(), SSE2_LANES);
+        for offset in (0..end).step_by(SSE2_LANES) {
+            // SAFETY: offset + 2 <= end, which is within all three slices.
+            unsafe {
+                let lhs_vector = _mm_loadu_si128(lhs.as_ptr().add(offset).cast());
+                let rhs_vector = _mm_loadu_si128(rhs.as_ptr().add(offset).cast());
+                let result = match op {
+                    BinaryOp::And => _mm_and_si128(lhs_vector, rhs_vector),
+                    BinaryOp::Or => _mm_or_si128(lhs_vector, rhs_vector),
+                    BinaryOp::Xor => _mm_xor_si128(lhs_vector, rhs_vector),
+                };
+                _mm_storeu_si128(out.as_mut_ptr().add(offset).cast(), result);
+            }
+        }
+        portable::binary(op, &lhs[end..], &rhs[end..], &mut out[end..]);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn binary_avx2(op: BinaryOp, lhs: &[u64], rhs: &[u64], out: &mut [u64]) {
+        let end = split(out.len(), AVX2_LANES);
+        for offset in (0..end).step_by(AVX2_LANES) {
+            let lhs_vector = _mm256_loadu_si256(lhs.as_ptr().add(offset).cast());
+            let rhs_vector = _mm256_loadu_si256(rhs.as_ptr().add(offset).cast());
+            let result = match op {
+                BinaryOp::And => _mm256_and_si256(lhs_vector, rhs_vector),
+                BinaryOp::Or => _mm256_or_si256(lhs_vector, rhs_vector),
+                BinaryOp::Xor => _mm256_xor_si256(lhs_vector, rhs_vector),
+            };
+            _mm256_storeu_si256(out.as_mut_ptr().add(offset).cast(), result);
+        }
+        portable::binary(op, &lhs[end..], &rhs[end..], &mut out[end..]);
+    }
+
+    pub(super) fn binary_assign_sse2(op: BinaryOp, lhs: &mut [u64], rhs: &[u64]) {
+        let end = split(lhs.len(), SSE2_LANES);
+        for offset in (0..end).step_by(SSE2_LANES) {
+            // SAFETY: offset + 2 <= end, which is within both slices.
+            unsafe {
+                let lhs_vector = _mm_loadu_si128(lhs.as_ptr().add(offset).cast());
+                let rhs_vector = _mm_loadu_si128(rhs.as_ptr().add(offset).cast());
+                let result = match op {
+                    BinaryOp::And => _mm_and_si128(lhs_vector, rhs_vector),
+                    BinaryOp::Or => _mm_or_si128(lhs_vector, rhs_vector),
+                    BinaryOp::Xor => _mm_xor_si128(lhs_vector, rhs_vector),
+                };
+                _mm_storeu_si128(lhs.as_mut_ptr().add(offset).cast(), result);
+            }
+        }
+        portable::binary_assign(op, &mut lhs[end..], &rhs[end..]);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn binary_assign_avx2(op: BinaryOp, lhs: &mut [u64], rhs: &[u64]) {
+        let end = split(lhs.len(), AVX2_LANES);
+        for offset in (0..end).step_by(AVX2_LANES) {
+            let lhs_vector = _mm256_loadu_si256(lhs.as_ptr().add(offset).cast());
+            let rhs_vector = _mm256_loadu_si256(rhs.as_ptr().add(offset).cast());
+            let result = match op {
+                BinaryOp::And => _mm256_and_si256(lhs_vector, rhs_vector),
+                BinaryOp::Or => _mm256_or_si256(lhs_vector, rhs_vector),
+                BinaryOp::Xor => _mm256_xor_si256(lhs_vector, rhs_vector),
+            };
+            _mm256_storeu_si256(lhs.as_mut_ptr().add(offset).cast(), result);
+        }
+        portable::binary_assign(op, &mut lhs[end..], &rhs[end..]);
+    }
+
+    pub(super) fn not_assign_sse2(words: &mut [u64]) {
+        let end = split(words.len(), SSE2_LANES);
+        // SAFETY: offset + 2 <= end, which is within the slice.
+        unsafe {
+            let ones = _mm_set1_epi8(-1);
+            for offset in (0..end).step_by(SSE2_LANES) {
+                let vector = _mm_loadu_si128(words.as_ptr().add(offset).cast());
+                _mm_storeu_si128(words.as_mut_ptr().add(offset).cast(), _mm_xor_si128(vector, ones));
+            }
+        }
+        portable::not_assign(&mut words[end..]);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn not_assign_avx2(words: &mut [u64]) {
+        let end = split(words.len(), AVX2_LANES);
+        let ones = _mm256_set1_epi8(-1);
+        for offset in (0..end).step_by(AVX2_LANES) {
+            let vector = _mm256_loadu_si256(words.as_ptr().add(offset).cast());
+            _mm256_storeu_si256(words.as_mut_ptr().add(offset).cast(), _mm256_xor_si256(vector, ones));
+        }
+        portable::not_assign(&mut words[end..]);
+    }
+
+    /// Counts the ones of every byte with shifts and masks, then sums the
+    /// bytes of each word.
+    pub(super) fn count_ones_sse2(words: &[u64]) -> usize {
+        let end = split(words.len(), SSE2_LANES);
+        let mut lanes = [0u64; 2];
+        // SAFETY: offset + 2 <= end, which is within the slice.  The masks
+        // drop every bit a shift carries across a byte boundary.
+        unsafe {
+            let zero = _mm_setzero_si128();
+            let pairs = _mm_set1_epi8(0x55);
+            let nibbles = _mm_set1_epi8(0x33);
+            let low_nibbles = _mm_set1_epi8(0x0F);
+            let mut sums = zero;
+            for offset in (0..end).step_by(SSE2_LANES) {
+                let vector = _mm_loadu_si128(words.as_ptr().add(offset).cast());
+                let vector = _mm_sub_epi8(vector, _mm_and_si128(_mm_srli_epi64(vector, 1), pairs));
+                let vector = _mm_add_epi8(
+                    _mm_and_si128(vector, nibbles),
+                    _mm_and_si128(_mm_srli_epi64(vector, 2), nibbles),
+                );
+                let vector = _mm_and_si128(_mm_add_epi8(vector, _mm_srli_epi64(vector, 4)), low_nibbles);
+                sums = _mm_add_epi64(sums, _mm_sad_epu8(vector, zero));
+            }
+            _mm_storeu_si128(lanes.as_mut_ptr().cast(), sums);
+        }
+        (lanes[0] + lanes[1]) as usize + portable::count_ones(&words[end..])
+    }
+
+    /// Looks up the ones of each nibble with a shuffle, then sums the bytes
+    /// of each word.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn count_ones_avx2(words: &[u64]) -> usize {
+        let end = split(words.len(), AVX2_LANES);
+        let nibble_ones = _mm256_setr_epi8(
+            0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, 0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4,
+        );
+        let low_nibbles = _mm256_set1_epi8(0x0F);
+        let zero = _mm256_setzero_si256();
+        let mut sums = zero;
+        for offset in (0..end).step_by(AVX2_LANES) {
+            let vector = _mm256_loadu_si256(words.as_ptr().add(offset).cast());
+            let low = _mm256_shuffle_epi8(nibble_ones, _mm256_and_si256(vector, low_nibbles));
+            let high = _mm256_shuffle_epi8(nibble_ones, _mm256_and_si256(_mm256_srli_epi16(vector, 4), low_nibbles));
+            sums = _mm256_add_epi64(sums, _mm256_sad_epu8(_mm256_add_epi8(low, high), zero));
+        }
+        let mut lanes = [0u64; 4];
+        _mm256_storeu_si256(lanes.as_mut_ptr().cast(), sums);
+        lanes.iter().sum::<u64>() as usize + portable::count_ones(&words[end..])
+    }
+
+    /// Bit `i` set when byte `i` of the words at `offset` differs from the
+    /// same byte of `skip`.
+    #[inline]
+    unsafe fn differences_sse2(words: &[u64], offset: usize, skip: __m128i) -> u32 {
+        let vector = _mm_loadu_si128(words.as_ptr().add(offset).cast());
+        !(_mm_movemask_epi8(_mm_cmpeq_epi8(vector, skip)) as u32) & 0xFFFF
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn differences_avx2(words: &[u64], offset: usize, skip: __m256i) -> u32 {
+        let vector = _mm256_loadu_si256(words.as_ptr().add(offset).cast());
+        !(_mm256_movemask_epi8(_mm256_cmpeq_epi8(vector, skip)) as u32)
+    }
+
+    pub(super) fn find_first_sse2(words: &[u64], skip: u64) -> Option<usize> {
+        let end = split(words.len(), SSE2_LANES);
+        // SAFETY: offset + 2 <= end, which is within the slice.
+        unsafe {
+            let skip_vector = _mm_set1_epi64x(skip as i64);
+            for offset in (0..end).step_by(SSE2_LANES) {
+                let differences = differences_sse2(words, offset, skip_vector);
+                if differences != 0 {
+                    return Some(offset + differences.trailing_zeros() as usize / 8)
+                }
+            }
+        }
+        portable::find_first(&words[end..], skip).map(|position| end + position)
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_first_avx2(words: &[u64], skip: u64) -> Option<usize> {
+        let end = split(words.len(), AVX2_LANES);
+        let skip_vector = _mm256_set1_epi64x(skip as i64);
+        for offset in (0..end).step_by(AVX2_LANES) {
+            let differences = differences_avx2(words, offset, skip_vector);
+            if differences != 0 {
+                return Some(offset + differences.trailing_zeros() as usize / 8)
+            }
+        }
+        portable::find_first(&words[end..], skip).map(|position| end + position)
+    }
+
+    pub(super) fn find_last_sse2(words: &[u64], skip: u64) -> Option<usize> {
+        let end = split(words.len(), SSE2_LANES);
+        if let Some(position) = portable::find_last(&words[end..], skip) {
+            return Some(end + position)
+        }
+        // SAFETY: offset + 2 <= end, which is within the slice.
+        unsafe {
+            let skip_vector = _mm_set1_epi64x(skip as i64);
+            for offset in (0..end).step_by(SSE2_LANES).rev() {
+                let differences = differences_sse2(words, offset, skip_vector);
+                if differences != 0 {
+                    return Some(offset + (31 - differences.leading_zeros() as usize) / 8)
+                }
+            }
+        }
+        None
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_last_avx2(words: &[u64], skip: u64) -> Option<usize> {
+        let end = split(words.len(), AVX2_LANES);
+        if let Some(position) = portable::find_last(&words[end..], skip) {
+            return Some(end + position)
+        }
+        let skip_vector = _mm256_set1_epi64x(skip as i64);
+        for offset in (0..end).step_by(AVX2_LANES).rev() {
+            let differences = differences_avx2(words, offset, skip_vector);
+            if differences != 0 {
+                return Some(offset + (31 - differences.leading_zeros() as usize) / 8)
+            }
+        }
+        None
+    }
+
+    pub(super) fn equal_sse2(lhs: &[u64], rhs: &[u64]) -> bool {
+        let end = split(lhs.len(), SSE2_LANES);
+        for offset in (0..end).step_by(SSE2_LANES) {
+            // SAFETY: offset + 2 <= end, which is within both slices.
+            let equal = unsafe {
+                let lhs_vector = _mm_loadu_si128(lhs.as_ptr().add(offset).cast());
+                let rhs_vector = _mm_loadu_si128(rhs.as_ptr().add(offset).cast());
+                _mm_movemask_epi8(_mm_cmpeq_epi8(lhs_vector, rhs_vector)) == 0xFFFF
+            };
+            if !equal {
+                return false
+            }
+        }
+        lhs[end..] == rhs[end..]
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn equal_avx2(lhs: &[u64], rhs: &[u64]) -> bool {
+        let end = split(lhs.len(), AVX2_LANES);
+        for offset in (0..end).step_by(AVX2_LANES) {
+            let lhs_vector = _mm256_loadu_si256(lhs.as_ptr().add(offset).cast());
+            let rhs_vector = _mm256_loadu_si256(rhs.as_ptr().add(offset).cast());
+            if _mm256_movemask_epi8(_mm256_cmpeq_epi8(lhs_vector, rhs_vector)) != -1 {
+                return false
+            }
+        }
+        lhs[end..] == rhs[end..]
+    }
+
+    /// `out[i] = input[i].reverse_bits()`, swapping neighbouring bits, then
+    /// pairs, then nibbles.  The masks drop every bit a shift carries across
+    /// a byte boundary.
+    pub(super) fn reverse_byte_bits_sse2(input: &[u8], out: &mut [u8]) {
+        let end = split(input.len(), 16);
+        for offset in (0..end).step_by(16) {
+            // SAFETY: offset + 16 <= end, which is within both slices.
+            unsafe {
+                let mut vector = _mm_loadu_si128(input.as_ptr().add(offset).cast());
+                for (shift, mask) in [(1, 0x55), (2, 0x33), (4, 0x0F)] {
+                    let (shift, mask) = (_mm_cvtsi32_si128(shift), _mm_set1_epi8(mask));
+                    vector = _mm_or_si128(
+                        _mm_and_si128(_mm_srl_epi16(vector, shift), mask),
+                        _mm_sll_epi16(_mm_and_si128(vector, mask), shift),
+                    );
+                }
+                _mm_storeu_si128(out.as_mut_ptr().add(offset).cast(), vector);
+            }
+        }
+        reverse_byte_bits(&input[end..], &mut out[end..]);
+    }
+
+    /// `out[i] = input[i].reverse_bits()`, looking up the reversal of each
+    /// nibble with a shuffle.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn reverse_byte_bits_avx2(input: &[u8], out: &mut [u8]) {
+        let end = split(input.len(), 32);
+        let reversed_nibbles = _mm256_setr_epi8(
+            0x0, 0x8, 0x4, 0xC, 0x2, 0xA, 0x6, 0xE, 0x1, 0x9, 0x5, 0xD, 0x3, 0xB, 0x7, 0xF, 0x0, 0x8, 0x4,
+            0xC, 0x2, 0xA, 0x6, 0xE, 0x1, 0x9, 0x5, 0xD, 0x3, 0xB, 0x7, 0xF,
+        );
+        let low_nibbles = _mm256_set1_epi8(0x0F);
+        for offset in (0..end).step_by(32) {
+            let vector = _mm256_loadu_si256(input.as_ptr().add(offset).cast());
+            let low = _mm256_shuffle_epi8(reversed_nibbles, _mm256_and_si256(vector, low_nibbles));
+            let high = _mm256_shuffle_epi8(
+                reversed_nibbles,
+                _mm256_and_si256(_mm256_srli_epi16(vector, 4), low_nibbles),
+            );
+            _mm256_storeu_si256(
+                out.as_mut_ptr().add(offset).cast(),
+                _mm256_or_si256(_mm256_slli_epi16(low, 4), high),
+            );
+        }
+        reverse_byte_bits(&input[end..], &mut out[end..]);
+    }
+
+    #[inline]
+    fn reverse_byte_bits(input: &[u8], out: &mut [u8]) {
+        for (out, input) in out.iter_mut().zip(input) {
+            *out = input.reverse_bits();
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate as bitman;
+use bitman::kernels::{
+    binary_assign_with, binary_with, count_ones_with, equal_with, find_first_with, find_last_with, not_assign_with,
+    pack_be_with, unpack_be_with, BinaryOp, Level,
+};
+use bitman::{Bit, Bits};
+use proptest::prelude::*;
+
+/// Every level this CPU can run, the portable one first.
+fn levels() -> Vec<Level> {
+    let mut levels = vec![Level::Portable];
+    #[cfg(target_arch = "x86_64")]
+    {
+        levels.push(Level::Sse2);
+        if std::is_x86_feature_detected!("avx2") {
+            levels.push(Level::Avx2);
+        }
+    }
+    levels
+}
+
+/// Words mostly of all zeros or all ones, as the searching kernels skip
+/// over, with random ones mixed in.
+fn words() -> impl Strategy<Value = Vec<u64>> {
+    prop::collection::vec(prop_oneof![Just(0u64), Just(u64::MAX), any::<u64>()], 0..40)
+}
+
+/// Runs `kernel` on every level and checks they all agree with the portable
+/// result.
+fn assert_same_on_every_level<Output: PartialEq + core::fmt::Debug>(kernel: impl Fn(Level) -> Output) {
+    let expected = kernel(Level::Portable);
+    for level in levels() {
+        assert_eq!(kernel(level), expected, "{level:?} differs from Portable");
+    }
+}
+
+proptest! {
+    #[test]
+    fn bitwise_kernels_match_on_every_level(lhs in words(), rhs in words()) {
+        let len = lhs.len().min(rhs.len());
+        for op in [BinaryOp::And, BinaryOp::Or, BinaryOp::Xor] {
+            assert_same_on_every_level(|level| {
+                let mut out = vec![0u64; len];
+                binary_with(level, op, &lhs[..len], &rhs[..len], &mut out);
+                out
+            });
+            assert_same_on_every_level(|level| {
+                let mut assigned = lhs[..len].to_vec();
+                binary_assign_with(level, op, &mut assigned, &rhs[..len]);
+                assigned
+            });
+        }
+        assert_same_on_every_level(|level| {
+            let mut out = lhs.clone();
+            not_assign_with(level, &mut out);
+            out
+        });
+        let mut expected_and = vec![0u64; len];
+        binary_with(Level::Portable, BinaryOp::And, &lhs[..len], &rhs[..len], &mut expected_and);
+        prop_assert!(expected_and.iter().zip(&lhs).zip(&rhs).all(|((&out, &lhs), &rhs)| out == lhs & rhs));
+    }
+
+    #[test]
+    fn counting_and_searching_kernels_match_on_every_level(words in words()) {
+        assert_same_on_every_level(|level| count_ones_with(level, &words));
+        for skip in [0, u64::MAX] {
+            assert_same_on_every_level(|level| find_first_with(level, &words, skip));
+            assert_same_on_every_level(|level| find_last_with(level, &words, skip));
+        }
+        prop_assert_eq!(
+            count_ones_with(Level::Portable, &words),
+            words.iter().map(|word| word.count_ones() as usize).sum::<usize>()
+        );
+        prop_assert_eq!(find_first_with(Level::Portable, &words, 0), words.iter().position(|&word| word != 0));
+        prop_assert_eq!(find_last_with(Level::Portable, &words, u64::MAX), words.iter().rposition(|&word| word != u64::MAX));
+    }
+
+    #[test]
+    fn equality_kernel_matches_on_every_level(lhs in words(), flip in any::<prop::sample::Index>(), bit in 0..64u32) {
+        assert_same_on_every_level(|level| equal_with(level, &lhs, &lhs));
+        if !lhs.is_empty() {
+            let mut rhs = lhs.clone();
+            let flipped = flip.index(rhs.len());
+            rhs[flipped] ^= 1 << bit;
+            assert_same_on_every_level(|level| equal_with(level, &lhs, &rhs));
+            prop_assert!(!equal_with(Level::Portable, &lhs, &rhs));
+        }
+    }
+
+    #[test]
+    fn packing_kernels_match_on_every_level(words in words()) {
+        assert_same_on_every_level(|level| {
+            let mut out = vec![0u8; words.len() * 8];
+            pack_be_with(level, &words, &mut out);
+            out
+        });
+        let mut bytes = vec![0u8; words.len() * 8];
+        pack_be_with(Level::Portable, &words, &mut bytes);
+        assert_same_on_every_level(|level| {
+            let mut out = vec![0u64; words.len()];
+            unpack_be_with(level, &bytes, &mut out);
+            out
+        });
+        let mut unpacked = vec![0u64; words.len()];
+        unpack_be_with(Level::Portable, &bytes, &mut unpacked);
+        prop_assert_eq!(unpacked, words);
+    }
+
+    #[test]
+    fn bits_operations_match_bit_by_bit_results(lhs in prop::collection::vec(any::<bool>(), 0..300), rhs in prop::collection::vec(any::<bool>(), 0..300)) {
+        let lhs_bits = Bits::new(&lhs.iter().map(|&bit| Bit(bit)).collect::<Vec<Bit>>());
+        let rhs_bits = Bits::new(&rhs.iter().map(|&bit| Bit(bit)).collect::<Vec<Bit>>());
+        let expected_and: Vec<Bit> = lhs.iter().zip(&rhs).map(|(&lhs, &rhs)| Bit(lhs & rhs)).collect();
+        prop_assert_eq!(lhs_bits.clone() & rhs_bits.clone(), Bits::new(&expected_and));
+        for op in [BinaryOp::And, BinaryOp::Or, BinaryOp::Xor] {
+            let mut assigned = lhs_bits.clone();
+            match op {
+                BinaryOp::And => assigned &= rhs_bits.clone(),
+                BinaryOp::Or => assigned |= rhs_bits.clone(),
+                BinaryOp::Xor => assigned ^= rhs_bits.clone(),
+            }
+            for (index, bit) in assigned.iter().enumerate() {
+                let expected = match (op, rhs.get(index)) {
+                    (_, None) => lhs[index],
+                    (BinaryOp::And, Some(&rhs)) => lhs[index] & rhs,
+                    (BinaryOp::Or, Some(&rhs)) => lhs[index] | rhs,
+                    (BinaryOp::Xor, Some(&rhs)) => lhs[index] ^ rhs,
+                };
+                prop_assert_eq!(bit.0, expected);
+            }
+        }
+        prop_assert_eq!(!lhs_bits.clone(), Bits::new(&lhs.iter().map(|&bit| Bit(!bit)).collect::<Vec<Bit>>()));
+        prop_assert_eq!(lhs_bits == rhs_bits, lhs == rhs);
+    }
+}
+
+#[test]
+fn to_be_bytes_packs_across_vector_widths() {
+    let bits = Bits::new(&(0..77).map(|index| Bit(index % 3 == 0)).collect::<Vec<Bit>>());
+    let bytes = bits.to_be_bytes();
+    assert_eq!(bytes.len(), 10);
+    assert_eq!(Bits::from_be_bytes(&bytes).slice(3..), bits);
+}
@@ -6,86 +6,112 @@
 #![feature(specialization)]
 #![cfg_attr(not(test), no_std)]
 
-use core::fmt::Debug;
-use core::{
-    mem::size_of,
-    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not},
-};
+use core::mem::size_of;
 extern crate alloc;
-use alloc::borrow::ToOwned;
+#[cfg(feature = "std")]
+extern crate std;
+use alloc::borrow::Cow;
 use alloc::vec;
 use num_traits::{CheckedShl, One, Zero};
 
+// The original modules end functions with an explicit `return`.
+#[allow(clippy::needless_return)]
 mod bit;
 pub use bit::*;
 
+#[allow(clippy::needless_return)]
 mod bits;
 pub use bits::*;
 
+mod bit_storage;
+
+mod scanning;
+
+mod kernels;
+
+mod positions;
+pub use positions::*;
+
+mod rank_select;
+pub use rank_select::*;
+
+mod elias_fano;
+pub use elias_fano::*;
+
+mod int_vec;
+pub use int_vec::*;
+
+mod block_codec;
+pub use block_codec::*;
+
+mod bit_set;
+pub use bit_set::*;
+
+mod roaring;
+pub use roaring::*;
+
+mod ewah;
+pub use ewah::*;
+
+mod bitmap_index;
+pub use bitmap_index::*;
+
+mod bloom_filter;
+pub use bloom_filter::*;
+
+mod atomic_bits;
+pub use atomic_bits::*;
+
+mod bitmap_allocator;
+pub use bitmap_allocator::*;
+
+mod tracked_bits;
+pub use tracked_bits::*;
+
+mod persistent_bits;
+pub use persistent_bits::*;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+
+mod sequences;
+pub use sequences::*;
+
+mod scalars;
+pub use scalars::*;
+
+mod tuples;
+
+mod integers;
+pub use integers::*;
+
+mod floats;
+pub use floats::*;
+
+mod minifloat;
+pub use minifloat::*;
+
+mod fixed_point;
+pub use fixed_point::*;
+
 #[cfg(test)]
 mod bitman_tests;
 #[cfg(test)]
+#[allow(unused_imports)]
 pub use bitman_tests::*;
 
-trait BitMan
-where
-    Self: Sized
-        + BitAnd<Output = Self>
-        + BitAndAssign
-        + BitOr
-        + BitOrAssign
-        + Not<Output = Self>
-        + Debug
-        + PartialEq
-        + ToOwned<Owned = Self>
-        + CheckedShl
-        + Zero
-        + One,
-{
-    #[inline]
-    fn bit_len(&self) -> usize {
-        size_of::<Self>() * 8
-    }
+pub trait BitMan {
+    fn bit_len(&self) -> usize;
 
-    #[inline]
-    fn bit(&self, index: &u32) -> Bit {
-        let mut mask = Self::one();
-        let offset: u32 = (self.bit_len() as u32 - 1) - *index;
-        if let Some(new_mask) = CheckedShl::checked_shl(&mask, offset) {
-            mask = new_mask;
-        } else {
-            panic!(
-                "Index {:?} passed to bit() is beyond the bounds of {:?}",
-                index, self
-            );
-        }
-        mask = self.to_owned() & mask;
-        Bit(mask != Self::zero())
-    }
+    fn bit(&self, index: &u32) -> Bit;
 
-    #[inline]
-    fn set_bit(&mut self, index: &u32, bit: &Bit) {
-        assert!(*index <= ((self.bit_len()) - 1).try_into().unwrap(), "Index out of range in call to set_bit()");
-        if **bit {
-            let mut mask: Self = Self::one();
-            if let Some(new_mask) = mask.checked_shl((self.bit_len() as u32 - 1) - index) {
-                mask = new_mask;
-                *self |= mask;
-            }
-        } else {
-            let mut mask: Self = Self::one();
-            if let Some(new_mask) = mask.checked_shl((self.bit_len() as u32 - 1) - index) {
-                mask = !new_mask;
-                *self &= mask;
-            }
-        }
-    }
+    fn set_bit(&mut self, index: &u32, bit: &Bit);
 
     #[inline]
     fn bits(&self) -> Bits {
         let mut output_value: Bits = Bits::new(&vec![Bit(false); self.bit_len()]);
         for current_index in 0..self.bit_len() as u32 {
-            (*output_value)[current_index as usize] = self.bit(&current_index);
+            output_value.set(current_index as usize, self.bit(&current_index));
         }
         output_value
     }
@@ -93,52 +119,70 @@ where
     #[inline]
     fn set_bits(&mut self, mut index: u32, bits: &Bits) {
         for current_bit in bits.iter() {
-            if current_bit.0 {
-                let mut mask: Self = Self::one();
-                if let Some(new_mask) =
-                    CheckedShl::checked_shl(&mask, self.bit_len() as u32 - index)
-                {
-                    mask = new_mask;
-                } else {
-                    mask = Self::zero();
-                }
-                *self &= !mask;
-            } else {
-                let mut mask: Self = Self::one();
-                if let Some(new_mask) =
-                    CheckedShl::checked_shl(&mask, self.bit_len() as u32 - index)
-                {
-                    mask = new_mask;
-                } else {
-                    mask = Self::zero();
-                }
-                *self |= mask;
-            }
+            self.set_bit(&index, &current_bit);
             index += 1;
         }
     }
-}
-
-impl BitMan for u8 {}
-
-impl BitMan for u16 {}
 
-impl BitMan for u32 {}
-
-impl BitMan for u64 {}
-
-impl BitMan for u128 {}
-
-impl BitMan for usize {}
+    #[inline]
+    fn iter_ones(&self) -> BitPositions<'_> {
+        BitPositions::new(Cow::Owned(self.bits()), Bit(true))
+    }
 
-impl BitMan for i8 {}
+    #[inline]
+    fn iter_zeros(&self) -> BitPositions<'_> {
+        BitPositions::new(Cow::Owned(self.bits()), Bit(false))
+    }
 
-impl BitMan for i16 {}
+    #[inline]
+    fn iter_runs(&self) -> BitRuns<'_> {
+        BitRuns::new(Cow::Owned(self.bits()))
+    }
+}
 
-impl BitMan for i32 {}
+macro_rules! impl_bitman_for_integers {
+    ($($integer_type:ty$(,)?)*) => {$(
+        impl BitMan for $integer_type {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                size_of::<Self>() * 8
+            }
 
-impl BitMan for i64 {}
+            #[inline]
+            fn bit(&self, index: &u32) -> Bit {
+                let mut mask = Self::one();
+                let offset: u32 = (self.bit_len() as u32 - 1) - *index;
+                if let Some(new_mask) = CheckedShl::checked_shl(&mask, offset) {
+                    mask = new_mask;
+                } else {
+                    panic!(
+                        "Index {:?} passed to bit() is beyond the bounds of {:?}",
+                        index, self
+                    );
+                }
+                mask &= *self;
+                Bit(mask != Self::zero())
+            }
 
-impl BitMan for i128 {}
+            #[inline]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                assert!(*index <= ((self.bit_len()) - 1).try_into().unwrap(), "Index out of range in call to set_bit()");
+                if **bit {
+                    let mut mask: Self = Self::one();
+                    if let Some(new_mask) = mask.checked_shl((self.bit_len() as u32 - 1) - index) {
+                        mask = new_mask;
+                        *self |= mask;
+                    }
+                } else {
+                    let mut mask: Self = Self::one();
+                    if let Some(new_mask) = mask.checked_shl((self.bit_len() as u32 - 1) - index) {
+                        mask = !new_mask;
+                        *self &= mask;
+                    }
+                }
+            }
+        })*
+    }
+}
 
-impl BitMan for isize {}
+impl_bitman_for_integers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
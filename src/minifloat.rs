@@ -0,0 +1,374 @@
+use crate::{Bit, BitMan, Bits, FloatClass, FloatParts};
+
+#[cfg(test)]
+mod minifloat_tests;
+
+/// How a value that falls between two representable values is rounded.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum RoundingMode {
+    NearestTiesToEven,
+    NearestTiesAwayFromZero,
+    TowardZero,
+    TowardPositive,
+    TowardNegative,
+}
+
+impl RoundingMode {
+    /// Decides whether a truncated magnitude must be incremented, given the
+    /// discarded remainder compared against half of the last kept unit.
+    #[inline]
+    pub(crate) fn rounds_up(
+        &self,
+        negative: bool,
+        truncated_is_odd: bool,
+        remainder: core::cmp::Ordering,
+        inexact: bool,
+    ) -> bool {
+        use core::cmp::Ordering::{Equal, Greater};
+        match self {
+            Self::NearestTiesToEven => {
+                remainder == Greater || (remainder == Equal && truncated_is_odd)
+            }
+            Self::NearestTiesAwayFromZero => remainder != core::cmp::Ordering::Less,
+            Self::TowardZero => false,
+            Self::TowardPositive => inexact && !negative,
+            Self::TowardNegative => inexact && negative,
+        }
+    }
+}
+
+/// A binary floating point value with `EXPONENT` exponent bits and `MANTISSA`
+/// stored mantissa bits, kept in a `Bits` laid out as sign, exponent, mantissa.
+///
+/// When `FINITE` is true the format has no infinities and only the all-ones
+/// exponent and mantissa pattern is NaN, as in the OCP FP8 E4M3 format.
+/// Values that would round to infinity in such a format become NaN instead.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct MiniFloat<const EXPONENT: u32, const MANTISSA: u32, const FINITE: bool = false> {
+    bits: Bits,
+}
+
+/// IEEE-754 binary16.
+pub type Float16 = MiniFloat<5, 10>;
+/// Brain floating point, the upper half of an `f32`.
+pub type BFloat16 = MiniFloat<8, 7>;
+/// 8-bit float with IEEE-754 style infinities and NaNs.
+pub type Float8E5M2 = MiniFloat<5, 2>;
+/// 8-bit float with IEEE-754 style infinities and NaNs.
+pub type Float8E4M3 = MiniFloat<4, 3>;
+/// The OCP 8-bit float without infinities, with a maximum of 448.
+pub type Float8E4M3Fn = MiniFloat<4, 3, true>;
+
+impl<const EXPONENT: u32, const MANTISSA: u32, const FINITE: bool> MiniFloat<EXPONENT, MANTISSA, FINITE> {
+    const BIT_LEN: usize = (1 + EXPONENT + MANTISSA) as usize;
+    const MAXIMUM_EXPONENT: u32 = (1 << EXPONENT) - 1;
+    const MANTISSA_MASK: u64 = (1 << MANTISSA) - 1;
+
+    #[inline]
+    #[must_use] pub fn new(bits: &Bits) -> Self {
+        assert!(
+            (2..=11).contains(&EXPONENT) && (1..=52).contains(&MANTISSA),
+            "MiniFloat supports 2 to 11 exponent bits and 1 to 52 mantissa bits"
+        );
+        assert_eq!(
+            bits.len(),
+            Self::BIT_LEN,
+            "Bits passed to MiniFloat::new() must be {} bits long",
+            Self::BIT_LEN
+        );
+        Self { bits: bits.clone() }
+    }
+
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    #[inline]
+    #[must_use] pub fn from_f32(value: f32, rounding_mode: RoundingMode) -> Self {
+        if value.is_nan() {
+            return Self::from_nan(value.sign(), value.mantissa() << 29)
+        }
+        Self::from_f64(f64::from(value), rounding_mode)
+    }
+
+    #[must_use] pub fn from_f64(value: f64, rounding_mode: RoundingMode) -> Self {
+        let sign = value.sign();
+        let negative = sign.0;
+        match value.float_class() {
+            FloatClass::QuietNan | FloatClass::SignalingNan => {
+                return Self::from_nan(sign, value.mantissa())
+            }
+            FloatClass::Infinite => return Self::infinity(sign),
+            FloatClass::Zero => return Self::from_parts(sign, 0, 0),
+            FloatClass::Normal | FloatClass::Subnormal => {}
+        }
+
+        // value == significand * 2^exponent exactly.
+        let significand: u64 = if value.biased_exponent() == 0 {
+            value.mantissa()
+        } else {
+            value.mantissa() | (1 << f64::MANTISSA_BITS)
+        };
+        let exponent = value.unbiased_exponent() - f64::MANTISSA_BITS as i32;
+        let magnitude_exponent = exponent + (63 - significand.leading_zeros() as i32);
+        let target_exponent = magnitude_exponent.max(1 - Self::EXPONENT_BIAS);
+        let shift = target_exponent - MANTISSA as i32 - exponent;
+
+        let mut rounded: u128 = if shift <= 0 {
+            u128::from(significand) << (-shift) as u32
+        } else if shift >= 64 {
+            u128::from(rounding_mode.rounds_up(
+                negative,
+                false,
+                core::cmp::Ordering::Less,
+                true,
+            ))
+        } else {
+            let truncated = significand >> shift;
+            let remainder = significand & ((1 << shift) - 1);
+            let half = 1u64 << (shift - 1);
+            u128::from(truncated)
+                + u128::from(rounding_mode.rounds_up(
+                    negative,
+                    truncated & 1 == 1,
+                    remainder.cmp(&half),
+                    remainder != 0,
+                ))
+        };
+        let mut biased_exponent = target_exponent + Self::EXPONENT_BIAS;
+        if rounded >> (MANTISSA + 1) != 0 {
+            rounded >>= 1;
+            biased_exponent += 1;
+        }
+        if rounded >> MANTISSA == 0 {
+            biased_exponent = 0;
+        }
+
+        let (maximum_exponent, maximum_mantissa) = Self::maximum_finite();
+        let rounded_mantissa = (rounded as u64) & Self::MANTISSA_MASK;
+        if biased_exponent > maximum_exponent as i32
+            || (biased_exponent == maximum_exponent as i32 && rounded_mantissa > maximum_mantissa)
+        {
+            let saturates = match rounding_mode {
+                RoundingMode::TowardZero => true,
+                RoundingMode::TowardPositive => negative,
+                RoundingMode::TowardNegative => !negative,
+                _ => false,
+            };
+            if saturates {
+                return Self::from_parts(sign, maximum_exponent, maximum_mantissa)
+            }
+            return Self::infinity(sign)
+        }
+        Self::from_parts(sign, biased_exponent as u32, rounded_mantissa)
+    }
+
+    #[inline]
+    #[must_use] pub fn to_f32(&self) -> f32 {
+        if self.is_nan() {
+            return f32::from_parts(self.sign(), 0xFF, self.nan_mantissa(f32::MANTISSA_BITS))
+        }
+        self.to_f64() as f32
+    }
+
+    #[must_use] pub fn to_f64(&self) -> f64 {
+        let sign = self.sign();
+        let mantissa = self.mantissa();
+        let magnitude = match self.float_class() {
+            FloatClass::QuietNan | FloatClass::SignalingNan => {
+                return f64::from_parts(sign, 0x7FF, self.nan_mantissa(f64::MANTISSA_BITS))
+            }
+            FloatClass::Infinite => f64::INFINITY,
+            FloatClass::Zero => 0.0,
+            FloatClass::Subnormal => {
+                mantissa as f64 * power_of_two(1 - Self::EXPONENT_BIAS - MANTISSA as i32)
+            }
+            FloatClass::Normal => {
+                (mantissa | (1 << MANTISSA)) as f64
+                    * power_of_two(self.unbiased_exponent() - MANTISSA as i32)
+            }
+        };
+        if sign.0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn is_nan(&self) -> bool {
+        matches!(
+            self.float_class(),
+            FloatClass::QuietNan | FloatClass::SignalingNan
+        )
+    }
+
+    #[inline]
+    fn maximum_finite() -> (u32, u64) {
+        if FINITE {
+            (Self::MAXIMUM_EXPONENT, Self::MANTISSA_MASK - 1)
+        } else {
+            (Self::MAXIMUM_EXPONENT - 1, Self::MANTISSA_MASK)
+        }
+    }
+
+    #[inline]
+    fn infinity(sign: Bit) -> Self {
+        if FINITE {
+            return Self::from_parts(sign, Self::MAXIMUM_EXPONENT, Self::MANTISSA_MASK)
+        }
+        Self::from_parts(sign, Self::MAXIMUM_EXPONENT, 0)
+    }
+
+    /// Builds a quiet NaN keeping the top bits of a 52 bit NaN mantissa.
+    #[inline]
+    fn from_nan(sign: Bit, source_mantissa: u64) -> Self {
+        if FINITE {
+            return Self::from_parts(sign, Self::MAXIMUM_EXPONENT, Self::MANTISSA_MASK)
+        }
+        let quiet_bit = 1 << (MANTISSA - 1);
+        Self::from_parts(
+            sign,
+            Self::MAXIMUM_EXPONENT,
+            (source_mantissa >> (f64::MANTISSA_BITS - MANTISSA)) | quiet_bit,
+        )
+    }
+
+    /// This NaN's mantissa widened to `mantissa_bits`, quieted.
+    #[inline]
+    fn nan_mantissa(&self, mantissa_bits: u32) -> u64 {
+        let quiet_bit = 1 << (mantissa_bits - 1);
+        if FINITE {
+            return quiet_bit
+        }
+        (self.mantissa() << (mantissa_bits - MANTISSA)) | quiet_bit
+    }
+}
+
+/// 2^exponent for exponents within the range of `f64`, subnormals included.
+#[inline]
+pub(crate) fn power_of_two(exponent: i32) -> f64 {
+    if exponent >= -1022 {
+        f64::from_parts(Bit(false), (exponent + 1023) as u32, 0)
+    } else {
+        f64::from_parts(Bit(false), 0, 1 << (exponent + 1074))
+    }
+}
+
+impl<const EXPONENT: u32, const MANTISSA: u32, const FINITE: bool> BitMan
+    for MiniFloat<EXPONENT, MANTISSA, FINITE>
+{
+    #[inline]
+    fn bit_len(&self) -> usize {
+        Self::BIT_LEN
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        self.bits[*index as usize]
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        self.bits.set(*index as usize, *bit);
+    }
+}
+
+impl<const EXPONENT: u32, const MANTISSA: u32, const FINITE: bool> FloatParts
+    for MiniFloat<EXPONENT, MANTISSA, FINITE>
+{
+    const EXPONENT_BITS: u32 = EXPONENT;
+    const MANTISSA_BITS: u32 = MANTISSA;
+    const EXPONENT_BIAS: i32 = (1 << (EXPONENT - 1)) - 1;
+
+    #[inline]
+    fn sign(&self) -> Bit {
+        self.bits[0]
+    }
+
+    #[inline]
+    fn biased_exponent(&self) -> u32 {
+        self.bits
+            .iter()
+            .skip(1)
+            .take(EXPONENT as usize)
+            .fold(0, |exponent, bit| (exponent << 1) | u32::from(bit.0))
+    }
+
+    #[inline]
+    fn mantissa(&self) -> u64 {
+        self.bits
+            .iter()
+            .skip(1 + EXPONENT as usize)
+            .fold(0, |mantissa, bit| (mantissa << 1) | u64::from(bit.0))
+    }
+
+    #[inline]
+    fn from_parts(sign: Bit, biased_exponent: u32, mantissa: u64) -> Self {
+        assert!(
+            biased_exponent <= Self::MAXIMUM_EXPONENT,
+            "Exponent {} is too large for a {} bit exponent",
+            biased_exponent,
+            EXPONENT
+        );
+        assert!(
+            mantissa <= Self::MANTISSA_MASK,
+            "Mantissa {} is too large for a {} bit mantissa",
+            mantissa,
+            MANTISSA
+        );
+        let mut bits = Bits::new(&[sign]);
+        bits.extend(biased_exponent.bits().iter().skip((32 - EXPONENT) as usize));
+        bits.extend(mantissa.bits().iter().skip((64 - MANTISSA) as usize));
+        Self::new(&bits)
+    }
+
+    #[inline]
+    fn nan_with_payload(sign: Bit, quiet: bool, payload: u64) -> Self {
+        if FINITE {
+            assert!(
+                quiet && payload == Self::MANTISSA_MASK >> 1,
+                "This format only has the all-ones NaN"
+            );
+            return Self::from_parts(sign, Self::MAXIMUM_EXPONENT, Self::MANTISSA_MASK)
+        }
+        let quiet_bit = 1u64 << (MANTISSA - 1);
+        assert!(
+            payload < quiet_bit,
+            "Payload {} does not fit below the quiet bit of a NaN",
+            payload
+        );
+        assert!(
+            quiet || payload != 0,
+            "A signaling NaN needs a non-zero payload"
+        );
+        let mantissa = if quiet { quiet_bit | payload } else { payload };
+        Self::from_parts(sign, Self::MAXIMUM_EXPONENT, mantissa)
+    }
+
+    #[inline]
+    fn float_class(&self) -> FloatClass {
+        let mantissa = self.mantissa();
+        match self.biased_exponent() {
+            0 if mantissa == 0 => FloatClass::Zero,
+            0 => FloatClass::Subnormal,
+            exponent if exponent == Self::MAXIMUM_EXPONENT => {
+                if FINITE {
+                    if mantissa == Self::MANTISSA_MASK {
+                        FloatClass::QuietNan
+                    } else {
+                        FloatClass::Normal
+                    }
+                } else if mantissa == 0 {
+                    FloatClass::Infinite
+                } else if mantissa >> (MANTISSA - 1) == 1 {
+                    FloatClass::QuietNan
+                } else {
+                    FloatClass::SignalingNan
+                }
+            }
+            _ => FloatClass::Normal,
+        }
+    }
+}
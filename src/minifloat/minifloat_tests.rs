@@ -0,0 +1,173 @@
+use crate as bitman;
+use bitman::{
+    BFloat16, Bit, BitMan, FloatClass, FloatParts, Float16, Float8E4M3, Float8E4M3Fn,
+    Float8E5M2, MiniFloat, RoundingMode,
+};
+use proptest::prelude::*;
+
+const NEAREST: RoundingMode = RoundingMode::NearestTiesToEven;
+
+#[test]
+fn float16_from_one_has_ieee_bit_pattern() {
+    let one = Float16::from_f32(1.0, NEAREST);
+    assert_eq!(*one.as_bits(), 0x3C00u16.bits());
+    assert_eq!(one.to_f32(), 1.0);
+}
+
+#[test]
+fn float16_largest_and_smallest_values() {
+    assert_eq!(Float16::new(&0x7BFFu16.bits()).to_f64(), 65504.0);
+    assert_eq!(Float16::new(&0x0001u16.bits()).to_f64(), 2f64.powi(-24));
+    assert_eq!(Float16::new(&0x0400u16.bits()).float_class(), FloatClass::Normal);
+    assert_eq!(Float16::new(&0x0001u16.bits()).float_class(), FloatClass::Subnormal);
+}
+
+#[test]
+fn float16_overflow_depends_on_rounding_mode() {
+    assert_eq!(
+        Float16::from_f64(70000.0, NEAREST).float_class(),
+        FloatClass::Infinite
+    );
+    assert_eq!(
+        Float16::from_f64(70000.0, RoundingMode::TowardZero).to_f64(),
+        65504.0
+    );
+    assert_eq!(
+        Float16::from_f64(-70000.0, RoundingMode::TowardPositive).to_f64(),
+        -65504.0
+    );
+    assert_eq!(
+        Float16::from_f64(-70000.0, RoundingMode::TowardNegative).to_f64(),
+        f64::NEG_INFINITY
+    );
+}
+
+#[test]
+fn float16_halfway_value_follows_rounding_mode() {
+    let halfway = 1.0 + 2f64.powi(-11);
+    let next_up = 1.0 + 2f64.powi(-10);
+    assert_eq!(Float16::from_f64(halfway, NEAREST).to_f64(), 1.0);
+    assert_eq!(
+        Float16::from_f64(halfway, RoundingMode::NearestTiesAwayFromZero).to_f64(),
+        next_up
+    );
+    assert_eq!(Float16::from_f64(halfway, RoundingMode::TowardZero).to_f64(), 1.0);
+    assert_eq!(
+        Float16::from_f64(halfway, RoundingMode::TowardPositive).to_f64(),
+        next_up
+    );
+    assert_eq!(
+        Float16::from_f64(-halfway, RoundingMode::TowardNegative).to_f64(),
+        -next_up
+    );
+}
+
+#[test]
+fn float16_rounds_into_subnormals_and_to_signed_zero() {
+    let smallest = 2f64.powi(-24);
+    assert_eq!(Float16::from_f64(smallest * 0.5, NEAREST).to_f64(), 0.0);
+    assert_eq!(
+        Float16::from_f64(smallest * 0.5, RoundingMode::NearestTiesAwayFromZero).to_f64(),
+        smallest
+    );
+    let negative_zero = Float16::from_f64(-1e-30, NEAREST);
+    assert_eq!(negative_zero.float_class(), FloatClass::Zero);
+    assert_eq!(negative_zero.sign(), Bit(true));
+    assert_eq!(
+        Float16::from_f64(smallest * 1.5, NEAREST).to_f64(),
+        smallest * 2.0
+    );
+}
+
+#[test]
+fn nan_payload_survives_float16_round_trip() {
+    let nan = f32::nan_with_payload(Bit(true), true, 0x15 << 13);
+    let half = Float16::from_f32(nan, NEAREST);
+    assert_eq!(half.float_class(), FloatClass::QuietNan);
+    assert_eq!(half.nan_payload(), Some(0x15));
+    assert_eq!(half.to_f32().to_bits(), nan.to_bits());
+}
+
+#[test]
+fn signaling_nan_is_quieted() {
+    let half = Float16::from_f64(f64::from_bits(0x7FF0_0000_0000_0001), NEAREST);
+    assert_eq!(half.float_class(), FloatClass::QuietNan);
+}
+
+#[test]
+fn float8_e4m3fn_has_no_infinity() {
+    assert_eq!(Float8E4M3Fn::new(&0x7Eu8.bits()).to_f32(), 448.0);
+    assert!(Float8E4M3Fn::new(&0x7Fu8.bits()).to_f32().is_nan());
+    assert!(Float8E4M3Fn::from_f32(f32::INFINITY, NEAREST).is_nan());
+    assert!(Float8E4M3Fn::from_f32(1000.0, NEAREST).is_nan());
+    assert_eq!(
+        Float8E4M3Fn::from_f32(1000.0, RoundingMode::TowardZero).to_f32(),
+        448.0
+    );
+}
+
+#[test]
+fn float8_e4m3_and_e5m2_largest_values() {
+    assert_eq!(Float8E4M3::new(&0x77u8.bits()).to_f32(), 240.0);
+    assert_eq!(Float8E5M2::new(&0x7Bu8.bits()).to_f32(), 57344.0);
+    assert_eq!(
+        Float8E5M2::from_f32(f32::NEG_INFINITY, NEAREST).float_class(),
+        FloatClass::Infinite
+    );
+}
+
+#[test]
+fn from_parts_matches_new() {
+    let value: MiniFloat<3, 4> = MiniFloat::from_parts(Bit(true), 0b011, 0b1000);
+    assert_eq!(*value.as_bits(), 0b1011_1000u8.bits());
+    assert_eq!(value.to_f64(), -1.5);
+}
+
+#[test]
+#[should_panic]
+fn new_with_wrong_length_panics() {
+    let _ = Float16::new(&0u8.bits());
+}
+
+#[test]
+fn every_float16_pattern_round_trips_through_f64() {
+    for raw_value in 0..=u16::MAX {
+        let half = Float16::new(&raw_value.bits());
+        let round_trip = Float16::from_f64(half.to_f64(), NEAREST);
+        if half.float_class() == FloatClass::SignalingNan {
+            assert_eq!(round_trip.float_class(), FloatClass::QuietNan);
+        } else {
+            assert_eq!(round_trip, half);
+        }
+    }
+}
+
+fn reference_bfloat16(value: f32) -> u16 {
+    let raw_value = value.to_bits();
+    ((raw_value + 0x7FFF + ((raw_value >> 16) & 1)) >> 16) as u16
+}
+
+proptest! {
+    #[test]
+    fn bfloat16_matches_reference_rounding(raw_value in any::<u32>()) {
+        let value = f32::from_bits(raw_value);
+        prop_assume!(!value.is_nan());
+        let bfloat = BFloat16::from_f32(value, NEAREST);
+        prop_assert_eq!(bfloat.as_bits().clone(), reference_bfloat16(value).bits());
+    }
+
+    #[test]
+    fn bfloat16_toward_zero_truncates(raw_value in any::<u32>()) {
+        let value = f32::from_bits(raw_value);
+        prop_assume!(!value.is_nan());
+        let bfloat = BFloat16::from_f32(value, RoundingMode::TowardZero);
+        prop_assert_eq!(bfloat.as_bits().clone(), value.bits().slice(..16));
+    }
+
+    #[test]
+    fn float16_directed_rounding_brackets_value(value in -70000.0f64..70000.0) {
+        let below = Float16::from_f64(value, RoundingMode::TowardNegative).to_f64();
+        let above = Float16::from_f64(value, RoundingMode::TowardPositive).to_f64();
+        prop_assert!(below <= value && value <= above);
+    }
+}
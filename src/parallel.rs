@@ -0,0 +1,192 @@
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+use crate::kernels::{self, BinaryOp};
+use crate::scanning::{count, find_first, find_last, read_field};
+use crate::Bits;
+
+#[cfg(test)]
+mod parallel_tests;
+
+/// Bits handled by one task: a whole number of 64 bit words.
+const CHUNK_LEN: usize = 64 * 1024;
+
+const CHUNK_WORDS: usize = CHUNK_LEN / 64;
+
+/// Parallel versions of the bulk `Bits` operations, available with the
+/// `rayon` feature.
+///
+/// Work is split into chunks of whole 64 bit words and every method returns
+/// exactly what its sequential counterpart does.
+impl Bits {
+    /// `self.clone() & rhs.clone()`, computed in parallel.
+    #[inline]
+    #[must_use] pub fn par_and(&self, rhs: &Self) -> Self {
+        self.par_combine(rhs, BinaryOp::And)
+    }
+
+    /// `self.clone() | rhs.clone()`, computed in parallel.
+    #[inline]
+    #[must_use] pub fn par_or(&self, rhs: &Self) -> Self {
+        self.par_combine(rhs, BinaryOp::Or)
+    }
+
+    /// `self.clone() ^ rhs.clone()`, computed in parallel.
+    #[inline]
+    #[must_use] pub fn par_xor(&self, rhs: &Self) -> Self {
+        self.par_combine(rhs, BinaryOp::Xor)
+    }
+
+    /// `!self.clone()`, computed in parallel.
+    #[must_use] pub fn par_not(&self) -> Self {
+        let mut output = self.clone();
+        output
+            .words_mut()
+            .par_chunks_mut(CHUNK_WORDS)
+            .for_each(kernels::not_assign);
+        output.clear_padding();
+        output
+    }
+
+    #[inline]
+    #[must_use] pub fn par_count_ones(&self) -> usize {
+        self.par_chunk_ranges()
+            .map(|(start, end)| count(self.words(), start, end, true))
+            .sum()
+    }
+
+    #[inline]
+    #[must_use] pub fn par_count_zeros(&self) -> usize {
+        self.len() - self.par_count_ones()
+    }
+
+    #[inline]
+    #[must_use] pub fn par_first_one(&self) -> Option<usize> {
+        self.par_find_first(true)
+    }
+
+    #[inline]
+    #[must_use] pub fn par_first_zero(&self) -> Option<usize> {
+        self.par_find_first(false)
+    }
+
+    #[inline]
+    #[must_use] pub fn par_last_one(&self) -> Option<usize> {
+        self.par_find_last(true)
+    }
+
+    #[inline]
+    #[must_use] pub fn par_last_zero(&self) -> Option<usize> {
+        self.par_find_last(false)
+    }
+
+    /// Positions of the set bits, in increasing order when collected.
+    #[inline]
+    pub fn par_iter_ones(&self) -> impl ParallelIterator<Item = usize> + '_ {
+        self.par_positions(true)
+    }
+
+    /// Positions of the clear bits, in increasing order when collected.
+    #[inline]
+    pub fn par_iter_zeros(&self) -> impl ParallelIterator<Item = usize> + '_ {
+        self.par_positions(false)
+    }
+
+    /// `self.to_be_bytes()`, computed in parallel.
+    #[must_use] pub fn par_to_be_bytes(&self) -> Vec<u8> {
+        let head_len = self.len() % 8;
+        let mut bytes = vec![0u8; (self.len() + 7) / 8];
+        if head_len > 0 {
+            bytes[0] = self.field(0, head_len as u32) as u8;
+        }
+        bytes[usize::from(head_len > 0)..]
+            .par_chunks_mut(CHUNK_LEN / 8)
+            .enumerate()
+            .for_each(|(chunk_index, byte_chunk)| {
+                let start = head_len + chunk_index * CHUNK_LEN;
+                let end = start + byte_chunk.len() * 8;
+                let mut words = [0u64; CHUNK_WORDS];
+                for (word, offset) in words.iter_mut().zip((start..end).step_by(64)) {
+                    *word = read_field(self.words(), offset, (end - offset).min(64) as u32);
+                }
+                let whole_words = byte_chunk.len() / 8;
+                kernels::pack_be(&words[..whole_words], &mut byte_chunk[..whole_words * 8]);
+                let tail = &mut byte_chunk[whole_words * 8..];
+                if !tail.is_empty() {
+                    let tail_len = tail.len();
+                    tail.copy_from_slice(&words[whole_words].reverse_bits().to_be_bytes()[..tail_len]);
+                }
+            });
+        bytes
+    }
+
+    /// `Bits::from_be_bytes(slice_of_bytes)`, computed in parallel.
+    #[must_use] pub fn par_from_be_bytes(slice_of_bytes: &[u8]) -> Self {
+        let mut output = Self::zeroed(slice_of_bytes.len() * 8);
+        output
+            .words_mut()
+            .par_chunks_mut(CHUNK_WORDS)
+            .zip(slice_of_bytes.par_chunks(CHUNK_LEN / 8))
+            .for_each(|(words, byte_chunk)| {
+                let whole_words = byte_chunk.len() / 8;
+                kernels::unpack_be(&byte_chunk[..whole_words * 8], &mut words[..whole_words]);
+                let tail = &byte_chunk[whole_words * 8..];
+                if !tail.is_empty() {
+                    let mut word_bytes = [0u8; 8];
+                    word_bytes[..tail.len()].copy_from_slice(tail);
+                    words[whole_words] = u64::from_be_bytes(word_bytes).reverse_bits();
+                }
+            });
+        output
+    }
+
+    fn par_combine(&self, rhs: &Self, op: BinaryOp) -> Self {
+        let mut output = Self::zeroed(self.len().min(rhs.len()));
+        let word_len = output.words().len();
+        output
+            .words_mut()
+            .par_chunks_mut(CHUNK_WORDS)
+            .zip(self.words()[..word_len].par_chunks(CHUNK_WORDS))
+            .zip(rhs.words()[..word_len].par_chunks(CHUNK_WORDS))
+            .for_each(|((output_chunk, lhs_chunk), rhs_chunk)| {
+                kernels::binary(op, lhs_chunk, rhs_chunk, output_chunk);
+            });
+        output.clear_padding();
+        output
+    }
+
+    /// The bit ranges handled by each task, in order.
+    #[inline]
+    fn par_chunk_ranges(&self) -> impl IndexedParallelIterator<Item = (usize, usize)> {
+        let len = self.len();
+        (0..(len + CHUNK_LEN - 1) / CHUNK_LEN)
+            .into_par_iter()
+            .map(move |chunk_index| (chunk_index * CHUNK_LEN, ((chunk_index + 1) * CHUNK_LEN).min(len)))
+    }
+
+    #[inline]
+    fn par_find_first(&self, target: bool) -> Option<usize> {
+        self.par_chunk_ranges()
+            .find_map_first(|(start, end)| find_first(self.words(), start, end, target))
+    }
+
+    #[inline]
+    fn par_find_last(&self, target: bool) -> Option<usize> {
+        self.par_chunk_ranges()
+            .find_map_last(|(start, end)| find_last(self.words(), start, end, target))
+    }
+
+    #[inline]
+    fn par_positions(&self, target: bool) -> impl ParallelIterator<Item = usize> + '_ {
+        self.par_chunk_ranges().flat_map_iter(move |(start, end)| {
+            let mut next = start;
+            core::iter::from_fn(move || {
+                let index = find_first(self.words(), next, end, target)?;
+                next = index + 1;
+                Some(index)
+            })
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits};
+use proptest::prelude::*;
+use rayon::prelude::*;
+
+/// Long enough to cover several parallel chunks and a partial word.
+fn striped_bits(len: usize, stride: usize) -> Bits {
+    Bits::new(&(0..len).map(|index| Bit(index % stride == 0 || index % 7_919 < 3)).collect::<Vec<Bit>>())
+}
+
+#[test]
+fn bitwise_operators_match_sequential_results() {
+    let lhs = striped_bits(200_003, 5);
+    let rhs = striped_bits(150_001, 3);
+    assert_eq!(lhs.par_and(&rhs), lhs.clone() & rhs.clone());
+    assert_eq!(lhs.par_or(&rhs), lhs.clone() | rhs.clone());
+    assert_eq!(lhs.par_xor(&rhs), lhs.clone() ^ rhs.clone());
+    assert_eq!(lhs.par_not(), !lhs.clone());
+}
+
+#[test]
+fn counting_and_searching_match_sequential_results() {
+    let mut bits = Bits::new(&vec![Bit(false); 300_000]);
+    assert_eq!(bits.par_first_one(), None);
+    bits.set(140_000, Bit(true));
+    bits.set(70_000, Bit(true));
+    bits.set(299_999, Bit(true));
+    assert_eq!(bits.par_count_ones(), 3);
+    assert_eq!(bits.par_count_zeros(), bits.count_zeros());
+    assert_eq!(bits.par_first_one(), Some(70_000));
+    assert_eq!(bits.par_last_one(), Some(299_999));
+    assert_eq!(bits.par_first_zero(), Some(0));
+    assert_eq!(bits.par_last_zero(), Some(299_998));
+    assert_eq!(bits.par_iter_ones().collect::<Vec<usize>>(), vec![70_000, 140_000, 299_999]);
+    assert_eq!(bits.par_iter_zeros().count(), bits.iter_zeros().count());
+}
+
+#[test]
+fn byte_conversions_match_sequential_results() {
+    let bits = striped_bits(131_077, 11);
+    let bytes = bits.to_be_bytes();
+    assert_eq!(bits.par_to_be_bytes(), bytes);
+    assert_eq!(Bits::par_from_be_bytes(&bytes), Bits::from_be_bytes(&bytes));
+    assert_eq!(Bits::new(&[]).par_to_be_bytes(), Vec::<u8>::new());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+    #[test]
+    fn matches_sequential_results(
+        lhs_bytes in proptest::collection::vec(any::<u8>(), 0..20_000),
+        rhs_bytes in proptest::collection::vec(any::<u8>(), 0..20_000),
+        trim in 0usize..8,
+    ) {
+        let lhs = Bits::from_be_bytes(&lhs_bytes);
+        let rhs = Bits::from_be_bytes(&rhs_bytes).slice(trim.min(rhs_bytes.len() * 8)..);
+        prop_assert_eq!(lhs.par_and(&rhs), lhs.clone() & rhs.clone());
+        prop_assert_eq!(lhs.par_xor(&rhs), lhs.clone() ^ rhs.clone());
+        prop_assert_eq!(rhs.par_count_ones(), rhs.count_ones());
+        prop_assert_eq!(rhs.par_first_zero(), rhs.first_zero());
+        prop_assert_eq!(rhs.par_last_one(), rhs.last_one());
+        prop_assert_eq!(rhs.par_iter_ones().collect::<Vec<usize>>(), rhs.iter_ones().collect::<Vec<usize>>());
+        prop_assert_eq!(rhs.par_to_be_bytes(), rhs.to_be_bytes());
+    }
+}
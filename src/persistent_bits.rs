@@ -0,0 +1,211 @@
+extern crate alloc;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod persistent_bits_tests;
+
+/// Bits held by each leaf chunk.
+const CHUNK_LEN: usize = 256;
+/// Children of each branch.
+const BRANCH_FACTOR: usize = 32;
+
+#[derive(Debug)]
+enum Node {
+    Leaf(Bits),
+    Branch(Vec<Arc<Node>>),
+}
+
+/// An immutable `Bits` stored as a tree of `Arc`-shared chunks.
+///
+/// `set_bit()` and `set_bits()` return a new version that copies only the
+/// chunks and branches on the path to the written bits and shares the rest
+/// with the original, so keeping many versions around is cheap.  Cloning
+/// copies a single pointer.
+#[derive(Debug, Clone)]
+pub struct PersistentBits {
+    root: Arc<Node>,
+    depth: u32,
+    len: usize,
+}
+
+impl PersistentBits {
+    /// `len` clear bits, all sharing one zero chunk.
+    #[must_use] pub fn new(len: usize) -> Self {
+        let depth = Self::depth_for(len);
+        let mut root = Arc::new(Node::Leaf(Bits::new(&[Bit(false); CHUNK_LEN])));
+        for _ in 0..depth {
+            root = Arc::new(Node::Branch(vec![root; BRANCH_FACTOR]));
+        }
+        Self { root, depth, len }
+    }
+
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    #[must_use] pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            return None
+        }
+        match self.chunk(index / CHUNK_LEN).as_ref() {
+            Node::Leaf(bits) => Some(bits[index % CHUNK_LEN]),
+            Node::Branch(_) => unreachable!(),
+        }
+    }
+
+    /// A new version with the bit at `index` set to `bit`.
+    #[inline]
+    #[must_use] pub fn set_bit(&self, index: usize, bit: Bit) -> Self {
+        self.set_bits(index, &Bits::new(&[bit]))
+    }
+
+    /// A new version with `bits` written starting at `index`.
+    #[must_use] pub fn set_bits(&self, index: usize, bits: &Bits) -> Self {
+        let end = index + bits.len();
+        assert!(
+            end <= self.len,
+            "Writing bits {index}..{end} is beyond the bounds of PersistentBits of length {}",
+            self.len
+        );
+        if bits.is_empty() {
+            return self.clone()
+        }
+        let root = Self::write(&self.root, self.capacity(), 0, index, bits);
+        Self {
+            root,
+            depth: self.depth,
+            len: self.len,
+        }
+    }
+
+    /// Number of leaf chunks holding the bits.
+    #[inline]
+    #[must_use] pub fn chunk_count(&self) -> usize {
+        (self.len + CHUNK_LEN - 1) / CHUNK_LEN
+    }
+
+    /// Number of chunks this version shares in memory with `other` at the
+    /// same position.
+    #[must_use] pub fn shared_chunks(&self, other: &Self) -> usize {
+        (0..self.chunk_count().min(other.chunk_count()))
+            .filter(|&chunk_index| Arc::ptr_eq(self.chunk(chunk_index), other.chunk(chunk_index)))
+            .count()
+    }
+
+    #[must_use] pub fn to_bits(&self) -> Bits {
+        let mut bits = Bits::new(&[]);
+        bits.reserve(self.chunk_count() * CHUNK_LEN);
+        for chunk_index in 0..self.chunk_count() {
+            if let Node::Leaf(chunk) = self.chunk(chunk_index).as_ref() {
+                bits.append(&mut chunk.clone());
+            }
+        }
+        bits.truncate(self.len);
+        bits
+    }
+
+    #[inline]
+    fn depth_for(len: usize) -> u32 {
+        let mut depth = 0;
+        let mut capacity = CHUNK_LEN;
+        while capacity < len {
+            capacity *= BRANCH_FACTOR;
+            depth += 1;
+        }
+        depth
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        CHUNK_LEN * BRANCH_FACTOR.pow(self.depth)
+    }
+
+    fn chunk(&self, chunk_index: usize) -> &Arc<Node> {
+        let mut node = &self.root;
+        let mut chunks_per_child = BRANCH_FACTOR.pow(self.depth);
+        while let Node::Branch(children) = node.as_ref() {
+            chunks_per_child /= BRANCH_FACTOR;
+            node = &children[(chunk_index / chunks_per_child) % BRANCH_FACTOR];
+        }
+        node
+    }
+
+    /// Copies `node`, which covers `capacity` bits from `node_start`, with
+    /// `bits` written from `index`.  Children outside the write are shared.
+    fn write(node: &Arc<Node>, capacity: usize, node_start: usize, index: usize, bits: &Bits) -> Arc<Node> {
+        let end = index + bits.len();
+        if end <= node_start || node_start + capacity <= index {
+            return Arc::clone(node)
+        }
+        match node.as_ref() {
+            Node::Leaf(chunk) => {
+                let mut chunk = chunk.clone();
+                let (from, to) = (index.max(node_start), end.min(node_start + capacity));
+                chunk.set_bits((from - node_start) as u32, &bits.slice(from - index..to - index));
+                Arc::new(Node::Leaf(chunk))
+            }
+            Node::Branch(children) => {
+                let child_capacity = capacity / BRANCH_FACTOR;
+                Arc::new(Node::Branch(
+                    children
+                        .iter()
+                        .enumerate()
+                        .map(|(child_index, child)| {
+                            Self::write(child, child_capacity, node_start + child_index * child_capacity, index, bits)
+                        })
+                        .collect(),
+                ))
+            }
+        }
+    }
+}
+
+impl Default for PersistentBits {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl PartialEq for PersistentBits {
+    /// Versions are equal when their bits are, whether or not they share
+    /// chunks.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && (0..self.chunk_count()).all(|chunk_index| {
+                let (lhs, rhs) = (self.chunk(chunk_index), other.chunk(chunk_index));
+                Arc::ptr_eq(lhs, rhs)
+                    || matches!((lhs.as_ref(), rhs.as_ref()), (Node::Leaf(lhs), Node::Leaf(rhs)) if lhs == rhs)
+            })
+    }
+}
+
+impl Eq for PersistentBits {}
+
+impl From<&Bits> for PersistentBits {
+    fn from(bits: &Bits) -> Self {
+        let mut persistent_bits = Self::new(bits.len());
+        if !bits.is_empty() {
+            persistent_bits.root = Self::write(&persistent_bits.root, persistent_bits.capacity(), 0, 0, bits);
+        }
+        persistent_bits
+    }
+}
+
+impl From<&PersistentBits> for Bits {
+    #[inline]
+    fn from(persistent_bits: &PersistentBits) -> Bits {
+        persistent_bits.to_bits()
+    }
+}
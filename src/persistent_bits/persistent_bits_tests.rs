@@ -0,0 +1,68 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits, PersistentBits};
+use proptest::prelude::*;
+
+#[test]
+fn set_bit_leaves_the_original_untouched() {
+    let original = PersistentBits::new(10_000);
+    let updated = original.set_bit(9_999, Bit(true));
+    assert_eq!(original.get(9_999), Some(Bit(false)));
+    assert_eq!(updated.get(9_999), Some(Bit(true)));
+    assert_eq!(updated.get(10_000), None);
+    assert_eq!(updated.to_bits().iter_ones().collect::<Vec<usize>>(), vec![9_999]);
+    assert_ne!(original, updated);
+}
+
+#[test]
+fn versions_share_unchanged_chunks() {
+    let original = PersistentBits::from(&Bits::new(&vec![Bit(false); 100_000]));
+    assert_eq!(original.chunk_count(), 391);
+    let updated = original.set_bits(1_000, &Bits::new(&[Bit(true); 300]));
+    assert_eq!(updated.shared_chunks(&original), 388);
+    let clone = updated.clone();
+    assert_eq!(clone.shared_chunks(&updated), 391);
+    assert_eq!(updated.to_bits().count_ones(), 300);
+}
+
+#[test]
+fn equality_compares_bits_not_sharing() {
+    let direct = PersistentBits::from(&Bits::from_be_bytes(&[0b1000_0001; 64]));
+    let mut built = PersistentBits::new(512);
+    for index in (0..512).step_by(8) {
+        built = built.set_bit(index, Bit(true)).set_bit(index + 7, Bit(true));
+    }
+    assert_eq!(built, direct);
+    assert_eq!(built.shared_chunks(&direct), 0);
+    assert_eq!(PersistentBits::default(), PersistentBits::new(0));
+    assert!(PersistentBits::default().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "beyond the bounds")]
+fn rejects_writes_past_the_end() {
+    let _ = PersistentBits::new(8).set_bits(7, &Bits::new(&[Bit(true); 2]));
+}
+
+proptest! {
+    #[test]
+    fn matches_mutable_bits(
+        bytes in proptest::collection::vec(any::<u8>(), 0..200),
+        writes in proptest::collection::vec((any::<usize>(), proptest::collection::vec(any::<bool>(), 0..300)), 0..10),
+    ) {
+        let mut expected = Bits::from_be_bytes(&bytes);
+        let mut persistent_bits = PersistentBits::from(&expected);
+        prop_assert_eq!(Bits::from(&persistent_bits), expected.clone());
+        for (index, values) in writes {
+            if expected.len() < values.len() {
+                continue
+            }
+            let index = index % (expected.len() - values.len() + 1);
+            let written = Bits::new(&values.iter().map(|&value| Bit(value)).collect::<Vec<Bit>>());
+            let previous = persistent_bits.clone();
+            persistent_bits = persistent_bits.set_bits(index, &written);
+            prop_assert_eq!(previous.to_bits(), expected.clone());
+            expected.set_bits(index as u32, &written);
+            prop_assert_eq!(persistent_bits.to_bits(), expected.clone());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+extern crate alloc;
+use alloc::borrow::Cow;
+use core::iter::FusedIterator;
+
+use crate::scanning::{find_first, find_last};
+use crate::{Bit, Bits};
+
+#[cfg(test)]
+mod positions_tests;
+
+/// Iterator over the indexes of the ones (or zeros) of a `Bits`, skipping
+/// a word at a time over stretches without a match.
+#[derive(Debug, Clone)]
+pub struct BitPositions<'a> {
+    bits: Cow<'a, Bits>,
+    target: bool,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> BitPositions<'a> {
+    #[inline]
+    pub(crate) fn new(bits: Cow<'a, Bits>, target: Bit) -> Self {
+        let back = bits.len();
+        Self {
+            bits,
+            target: target.0,
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl Iterator for BitPositions<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let index = find_first(self.bits.words(), self.front, self.back, self.target)?;
+        self.front = index + 1;
+        Some(index)
+    }
+}
+
+impl DoubleEndedIterator for BitPositions<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        let index = find_last(self.bits.words(), self.front, self.back, self.target)?;
+        self.back = index;
+        Some(index)
+    }
+}
+
+impl FusedIterator for BitPositions<'_> {}
+
+/// Iterator over the runs of equal bits of a `Bits` as `(value, start, len)`.
+#[derive(Debug, Clone)]
+pub struct BitRuns<'a> {
+    bits: Cow<'a, Bits>,
+    front: usize,
+}
+
+impl<'a> BitRuns<'a> {
+    #[inline]
+    pub(crate) fn new(bits: Cow<'a, Bits>) -> Self {
+        Self { bits, front: 0 }
+    }
+}
+
+impl Iterator for BitRuns<'_> {
+    type Item = (Bit, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.front;
+        let value = self.bits.get(start)?.0;
+        let end = find_first(self.bits.words(), start, self.bits.len(), !value).unwrap_or(self.bits.len());
+        self.front = end;
+        Some((Bit(value), start, end - start))
+    }
+}
+
+impl FusedIterator for BitRuns<'_> {}
@@ -0,0 +1,71 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits};
+use proptest::prelude::*;
+
+#[test]
+fn iter_ones_of_bits_yields_set_indexes() {
+    let bits = Bits::from_be_bytes(&[0b1000_0001, 0, 0, 0b0100_0000]);
+    assert_eq!(bits.iter_ones().collect::<Vec<usize>>(), vec![0, 7, 25]);
+    assert_eq!(bits.iter_ones().rev().collect::<Vec<usize>>(), vec![25, 7, 0]);
+    assert_eq!(bits.iter_zeros().count(), 29);
+}
+
+#[test]
+fn iter_ones_meets_in_the_middle_from_both_ends() {
+    let bits = Bits::from_be_bytes(&[0xFF]);
+    let mut ones = bits.iter_ones();
+    assert_eq!(ones.next(), Some(0));
+    assert_eq!(ones.next_back(), Some(7));
+    assert_eq!(ones.by_ref().collect::<Vec<usize>>(), vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(ones.next(), None);
+    assert_eq!(ones.next_back(), None);
+}
+
+#[test]
+fn iter_ones_of_primitives_matches_bit_indexes() {
+    assert_eq!(0x8001u16.iter_ones().collect::<Vec<usize>>(), vec![0, 15]);
+    assert_eq!(0u64.iter_ones().next(), None);
+    assert_eq!(u8::MAX.iter_zeros().next(), None);
+    assert_eq!(true.iter_ones().collect::<Vec<usize>>(), vec![0]);
+}
+
+#[test]
+fn iter_runs_covers_every_bit() {
+    let runs: Vec<(Bit, usize, usize)> = 0b1110_0100u8.iter_runs().collect();
+    assert_eq!(
+        runs,
+        vec![
+            (Bit(true), 0, 3),
+            (Bit(false), 3, 2),
+            (Bit(true), 5, 1),
+            (Bit(false), 6, 2),
+        ]
+    );
+    assert_eq!(Bits::new(&[]).iter_runs().next(), None);
+}
+
+#[test]
+fn iter_ones_of_large_sparse_mask() {
+    let mut mask = Bits::new(&[Bit(false); 4096]);
+    mask.set(17, Bit(true));
+    mask.set(4095, Bit(true));
+    assert_eq!(mask.iter_ones().collect::<Vec<usize>>(), vec![17, 4095]);
+}
+
+proptest! {
+    #[test]
+    fn iterators_match_naive_scan(pattern in proptest::collection::vec(any::<bool>(), 0..150)) {
+        let bits = Bits::new(&pattern.iter().map(|&value| Bit(value)).collect::<Vec<Bit>>());
+        let ones: Vec<usize> = (0..pattern.len()).filter(|&index| pattern[index]).collect();
+        let zeros: Vec<usize> = (0..pattern.len()).filter(|&index| !pattern[index]).collect();
+        prop_assert_eq!(bits.iter_ones().collect::<Vec<usize>>(), ones.clone());
+        prop_assert_eq!(bits.iter_zeros().rev().collect::<Vec<usize>>(), zeros.into_iter().rev().collect::<Vec<usize>>());
+        let mut rebuilt = Vec::new();
+        for (value, start, length) in bits.iter_runs() {
+            prop_assert_eq!(start, rebuilt.len());
+            prop_assert!(length > 0);
+            rebuilt.extend(core::iter::repeat(value.0).take(length));
+        }
+        prop_assert_eq!(rebuilt, pattern);
+    }
+}
@@ -0,0 +1,201 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use crate::{Bit, Bits};
+
+#[cfg(test)]
+mod rank_select_tests;
+
+const WORDS_PER_SUPERBLOCK: usize = 8;
+const SUPERBLOCK_BITS: usize = WORDS_PER_SUPERBLOCK * 64;
+
+/// An immutable copy of a `Bits` with rank and select support.
+///
+/// The bits are packed into 64 bit words.  A superblock table holds the ones
+/// before every 512 bits and a block table holds the ones before every word
+/// within its superblock, so `rank1()` is three lookups and a popcount and
+/// `select1()` is a binary search over superblocks.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct RankSelect {
+    words: Vec<u64>,
+    superblock_ranks: Vec<u64>,
+    block_ranks: Vec<u16>,
+    len: usize,
+    ones: usize,
+}
+
+impl RankSelect {
+    #[must_use] pub fn new(bits: &Bits) -> Self {
+        Self::from_words(bits.words().to_vec(), bits.len())
+    }
+
+    /// Builds the rank tables over `len` bits already packed into words.
+    /// Bits past `len` must be zero.
+    pub(crate) fn from_words(words: Vec<u64>, len: usize) -> Self {
+        let mut superblock_ranks = Vec::with_capacity(words.len() / WORDS_PER_SUPERBLOCK + 1);
+        let mut block_ranks = Vec::with_capacity(words.len());
+        let mut ones = 0usize;
+        let mut ones_in_superblock = 0u16;
+        for (word_index, word) in words.iter().enumerate() {
+            if word_index % WORDS_PER_SUPERBLOCK == 0 {
+                superblock_ranks.push(ones as u64);
+                ones_in_superblock = 0;
+            }
+            block_ranks.push(ones_in_superblock);
+            ones_in_superblock += word.count_ones() as u16;
+            ones += word.count_ones() as usize;
+        }
+        Self {
+            words,
+            superblock_ranks,
+            block_ranks,
+            len,
+            ones,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    #[inline]
+    #[must_use] pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    #[must_use] pub fn count_ones(&self) -> usize {
+        self.ones
+    }
+
+    #[inline]
+    #[must_use] pub fn count_zeros(&self) -> usize {
+        self.len - self.ones
+    }
+
+    #[inline]
+    #[must_use] pub fn get(&self, index: usize) -> Option<Bit> {
+        if index >= self.len {
+            return None
+        }
+        Some(Bit((self.words[index / 64] >> (index % 64)) & 1 == 1))
+    }
+
+    /// Number of ones before `index`.
+    #[inline]
+    #[must_use] pub fn rank1(&self, index: usize) -> usize {
+        assert!(
+            index <= self.len,
+            "Index {} passed to rank1() is beyond the end of {} bits",
+            index,
+            self.len
+        );
+        let word_index = index / 64;
+        if word_index == self.words.len() {
+            return self.ones
+        }
+        let below_index = self.words[word_index] & ((1u64 << (index % 64)) - 1);
+        self.superblock_ranks[word_index / WORDS_PER_SUPERBLOCK] as usize
+            + self.block_ranks[word_index] as usize
+            + below_index.count_ones() as usize
+    }
+
+    /// Number of zeros before `index`.
+    #[inline]
+    #[must_use] pub fn rank0(&self, index: usize) -> usize {
+        index - self.rank1(index)
+    }
+
+    /// Index of the one with `rank` ones before it.
+    #[must_use] pub fn select1(&self, rank: usize) -> Option<usize> {
+        if rank >= self.ones {
+            return None
+        }
+        Some(self.select(rank, true))
+    }
+
+    /// Index of the zero with `rank` zeros before it.
+    #[must_use] pub fn select0(&self, rank: usize) -> Option<usize> {
+        if rank >= self.count_zeros() {
+            return None
+        }
+        Some(self.select(rank, false))
+    }
+
+    /// Bytes used by the rank tables, on top of the packed bits.
+    #[inline]
+    #[must_use] pub fn overhead_bytes(&self) -> usize {
+        self.superblock_ranks.len() * size_of::<u64>() + self.block_ranks.len() * size_of::<u16>()
+    }
+
+    /// Bytes used by the packed bits and the rank tables together.
+    #[inline]
+    #[must_use] pub fn size_in_bytes(&self) -> usize {
+        self.words.len() * size_of::<u64>() + self.overhead_bytes()
+    }
+
+    #[inline]
+    fn superblock_rank(&self, superblock: usize, ones: bool) -> usize {
+        let superblock_ones = self.superblock_ranks[superblock] as usize;
+        if ones {
+            superblock_ones
+        } else {
+            superblock * SUPERBLOCK_BITS - superblock_ones
+        }
+    }
+
+    #[inline]
+    fn block_rank(&self, word_index: usize, ones: bool) -> usize {
+        let block_ones = self.block_ranks[word_index] as usize;
+        if ones {
+            block_ones
+        } else {
+            (word_index % WORDS_PER_SUPERBLOCK) * 64 - block_ones
+        }
+    }
+
+    fn select(&self, rank: usize, ones: bool) -> usize {
+        let mut low = 0;
+        let mut high = self.superblock_ranks.len();
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+            if self.superblock_rank(middle, ones) <= rank {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        let superblock = low;
+        let rank_in_superblock = rank - self.superblock_rank(superblock, ones);
+        let last_word = ((superblock + 1) * WORDS_PER_SUPERBLOCK).min(self.words.len());
+        let mut word_index = superblock * WORDS_PER_SUPERBLOCK;
+        while word_index + 1 < last_word
+            && self.block_rank(word_index + 1, ones) <= rank_in_superblock
+        {
+            word_index += 1;
+        }
+        let mut word = if ones {
+            self.words[word_index]
+        } else {
+            !self.words[word_index]
+        };
+        for _ in 0..rank_in_superblock - self.block_rank(word_index, ones) {
+            word &= word - 1;
+        }
+        word_index * 64 + word.trailing_zeros() as usize
+    }
+}
+
+impl From<&Bits> for RankSelect {
+    #[inline]
+    fn from(bits: &Bits) -> Self {
+        Self::new(bits)
+    }
+}
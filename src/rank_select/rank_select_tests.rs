@@ -0,0 +1,84 @@
+use crate as bitman;
+use bitman::{Bit, Bits, RankSelect};
+use proptest::prelude::*;
+
+fn bits_from(pattern: &[bool]) -> Bits {
+    Bits::new(&pattern.iter().map(|&value| Bit(value)).collect::<Vec<Bit>>())
+}
+
+#[test]
+fn rank_and_select_on_small_bits() {
+    let rank_select = RankSelect::new(&Bits::from_be_bytes(&[0b1001_0000, 0b0000_0001]));
+    assert_eq!(rank_select.len(), 16);
+    assert_eq!(rank_select.count_ones(), 3);
+    assert_eq!(rank_select.rank1(0), 0);
+    assert_eq!(rank_select.rank1(1), 1);
+    assert_eq!(rank_select.rank1(4), 2);
+    assert_eq!(rank_select.rank1(16), 3);
+    assert_eq!(rank_select.rank0(16), 13);
+    assert_eq!(rank_select.select1(0), Some(0));
+    assert_eq!(rank_select.select1(2), Some(15));
+    assert_eq!(rank_select.select1(3), None);
+    assert_eq!(rank_select.select0(0), Some(1));
+    assert_eq!(rank_select.select0(12), Some(14));
+    assert_eq!(rank_select.select0(13), None);
+    assert_eq!(rank_select.get(3), Some(Bit(true)));
+    assert_eq!(rank_select.get(16), None);
+}
+
+#[test]
+fn empty_rank_select() {
+    let rank_select = RankSelect::new(&Bits::new(&[]));
+    assert!(rank_select.is_empty());
+    assert_eq!(rank_select.rank1(0), 0);
+    assert_eq!(rank_select.select1(0), None);
+    assert_eq!(rank_select.select0(0), None);
+}
+
+#[test]
+#[should_panic]
+fn rank_beyond_end_panics() {
+    let _ = RankSelect::new(&Bits::from_be_bytes(&[0xFF])).rank1(9);
+}
+
+#[test]
+fn overhead_is_small_fraction_of_bits() {
+    let rank_select = RankSelect::from(&Bits::new(&vec![Bit(true); 1 << 16]));
+    assert_eq!(rank_select.overhead_bytes(), 128 * 8 + 1024 * 2);
+    assert_eq!(rank_select.size_in_bytes(), 8192 + rank_select.overhead_bytes());
+    assert_eq!(rank_select.select1(65_535), Some(65_535));
+    assert_eq!(rank_select.rank1(40_000), 40_000);
+}
+
+proptest! {
+    #[test]
+    fn rank_and_select_match_naive(pattern in proptest::collection::vec(any::<bool>(), 0..2000)) {
+        let rank_select = RankSelect::new(&bits_from(&pattern));
+        let ones: Vec<usize> = (0..pattern.len()).filter(|&index| pattern[index]).collect();
+        let zeros: Vec<usize> = (0..pattern.len()).filter(|&index| !pattern[index]).collect();
+        let mut rank = 0;
+        for (index, &value) in pattern.iter().enumerate() {
+            prop_assert_eq!(rank_select.rank1(index), rank);
+            rank += usize::from(value);
+        }
+        prop_assert_eq!(rank_select.rank1(pattern.len()), rank);
+        for (rank, &index) in ones.iter().enumerate() {
+            prop_assert_eq!(rank_select.select1(rank), Some(index));
+        }
+        for (rank, &index) in zeros.iter().enumerate() {
+            prop_assert_eq!(rank_select.select0(rank), Some(index));
+        }
+    }
+
+    #[test]
+    fn sparse_select_crosses_superblocks(positions in proptest::collection::btree_set(0usize..5000, 0..40)) {
+        let mut pattern = vec![false; 5000];
+        for &position in &positions {
+            pattern[position] = true;
+        }
+        let rank_select = RankSelect::new(&bits_from(&pattern));
+        for (rank, &position) in positions.iter().enumerate() {
+            prop_assert_eq!(rank_select.select1(rank), Some(position));
+        }
+    }
+}
@@ -0,0 +1,894 @@
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+use core::iter::FusedIterator;
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
+use core::slice;
+
+use crate::{Bit, Bits};
+
+#[cfg(test)]
+mod roaring_tests;
+
+const ARRAY_MAX_LEN: usize = 4096;
+const BITMAP_WORDS: usize = 1024;
+const SERIAL_COOKIE: u32 = 12347;
+const SERIAL_COOKIE_NO_RUN_CONTAINER: u32 = 12346;
+const NO_OFFSET_THRESHOLD: usize = 4;
+
+/// Returned when bytes passed to `RoaringBitmap::deserialize()` are not a
+/// valid Roaring portable serialization.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct InvalidRoaringFormat {
+    pub reason: &'static str,
+}
+
+impl Display for InvalidRoaringFormat {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "Invalid Roaring bitmap: {}", self.reason)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum SetOperation {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+/// The low 16 bits of the values that share one set of high 16 bits.
+///
+/// Bitmap and run containers cache their number of values, as counting them
+/// means popcounting 1024 words or summing every run.
+#[derive(Debug, Clone)]
+enum Container {
+    /// Sorted values, at most `ARRAY_MAX_LEN` of them.
+    Array(Vec<u16>),
+    /// One bit per possible value.
+    Bitmap {
+        words: Box<[u64; BITMAP_WORDS]>,
+        len: usize,
+    },
+    /// Sorted, non-touching runs as `(start, length - 1)`.
+    Run { runs: Vec<(u16, u16)>, len: usize },
+}
+
+impl Container {
+    #[inline]
+    fn bitmap(words: Box<[u64; BITMAP_WORDS]>) -> Self {
+        let len = words.iter().map(|word| word.count_ones() as usize).sum();
+        Self::Bitmap { words, len }
+    }
+
+    #[inline]
+    fn runs(runs: Vec<(u16, u16)>) -> Self {
+        let len = runs.iter().map(|&(_, length)| usize::from(length) + 1).sum();
+        Self::Run { runs, len }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            Self::Array(values) => values.len(),
+            Self::Bitmap { len, .. } | Self::Run { len, .. } => *len,
+        }
+    }
+
+    #[inline]
+    fn contains(&self, low: u16) -> bool {
+        match self {
+            Self::Array(values) => values.binary_search(&low).is_ok(),
+            Self::Bitmap { words, .. } => (words[usize::from(low / 64)] >> (low % 64)) & 1 == 1,
+            Self::Run { runs, .. } => {
+                let after = runs.partition_point(|&(start, _)| start <= low);
+                after > 0 && {
+                    let (start, length) = runs[after - 1];
+                    u32::from(low) <= u32::from(start) + u32::from(length)
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, low: u16) -> bool {
+        match self {
+            Self::Array(values) => match values.binary_search(&low) {
+                Ok(_) => false,
+                Err(position) if values.len() < ARRAY_MAX_LEN => {
+                    values.insert(position, low);
+                    true
+                }
+                Err(_) => {
+                    let mut words = self.to_bitmap();
+                    words[usize::from(low / 64)] |= 1 << (low % 64);
+                    *self = Self::Bitmap {
+                        words,
+                        len: ARRAY_MAX_LEN + 1,
+                    };
+                    true
+                }
+            },
+            Self::Bitmap { words, len } => {
+                let mask = 1u64 << (low % 64);
+                let word = &mut words[usize::from(low / 64)];
+                let newly_inserted = *word & mask == 0;
+                *word |= mask;
+                *len += usize::from(newly_inserted);
+                newly_inserted
+            }
+            Self::Run { runs, len } => {
+                let after = runs.partition_point(|&(start, _)| start <= low);
+                let joins_before = after > 0 && {
+                    let (start, length) = runs[after - 1];
+                    let end = u32::from(start) + u32::from(length);
+                    if u32::from(low) <= end {
+                        return false
+                    }
+                    end + 1 == u32::from(low)
+                };
+                let joins_after = after < runs.len() && u32::from(runs[after].0) == u32::from(low) + 1;
+                match (joins_before, joins_after) {
+                    (true, true) => {
+                        runs[after - 1].1 += runs[after].1 + 2;
+                        runs.remove(after);
+                    }
+                    (true, false) => runs[after - 1].1 += 1,
+                    (false, true) => runs[after] = (low, runs[after].1 + 1),
+                    (false, false) => runs.insert(after, (low, 0)),
+                }
+                *len += 1;
+                true
+            }
+        }
+    }
+
+    fn remove(&mut self, low: u16) -> bool {
+        match self {
+            Self::Array(values) => match values.binary_search(&low) {
+                Ok(position) => {
+                    values.remove(position);
+                    true
+                }
+                Err(_) => false,
+            },
+            Self::Bitmap { words, len } => {
+                let mask = 1u64 << (low % 64);
+                let word = &mut words[usize::from(low / 64)];
+                if *word & mask == 0 {
+                    return false
+                }
+                *word &= !mask;
+                *len -= 1;
+                if *len <= ARRAY_MAX_LEN {
+                    *self = Self::Array(self.iter().collect());
+                }
+                true
+            }
+            Self::Run { runs, len } => {
+                let after = runs.partition_point(|&(start, _)| start <= low);
+                if after == 0 {
+                    return false
+                }
+                let (start, length) = runs[after - 1];
+                let end = u32::from(start) + u32::from(length);
+                if u32::from(low) > end {
+                    return false
+                }
+                if length == 0 {
+                    runs.remove(after - 1);
+                } else if low == start {
+                    runs[after - 1] = (start + 1, length - 1);
+                } else if u32::from(low) == end {
+                    runs[after - 1].1 -= 1;
+                } else {
+                    runs[after - 1].1 = low - start - 1;
+                    runs.insert(after, (low + 1, (end - u32::from(low) - 1) as u16));
+                }
+                *len -= 1;
+                true
+            }
+        }
+    }
+
+    #[inline]
+    fn iter(&self) -> ContainerIter<'_> {
+        match self {
+            Self::Array(values) => ContainerIter::Array(values.iter()),
+            Self::Bitmap { words, .. } => ContainerIter::Bitmap {
+                words,
+                word_index: 0,
+                word: words[0],
+            },
+            Self::Run { runs, .. } => ContainerIter::Run {
+                runs: runs.iter(),
+                next: 0,
+                end: 0,
+            },
+        }
+    }
+
+    fn to_bitmap(&self) -> Box<[u64; BITMAP_WORDS]> {
+        if let Self::Bitmap { words, .. } = self {
+            return words.clone()
+        }
+        let mut words = Box::new([0u64; BITMAP_WORDS]);
+        for low in self.iter() {
+            words[usize::from(low / 64)] |= 1 << (low % 64);
+        }
+        words
+    }
+
+    /// The same values as an array or bitmap container, whichever is
+    /// required by its length.
+    fn without_runs(&self) -> Self {
+        let len = self.len();
+        if len <= ARRAY_MAX_LEN {
+            Self::Array(self.iter().collect())
+        } else {
+            Self::Bitmap {
+                words: self.to_bitmap(),
+                len,
+            }
+        }
+    }
+
+    /// The same values in whichever container serializes smallest.
+    fn optimized(&self) -> Self {
+        let len = self.len();
+        let mut runs: Vec<(u16, u16)> = Vec::new();
+        for low in self.iter() {
+            match runs.last_mut() {
+                Some((start, length)) if u32::from(*start) + u32::from(*length) + 1 == u32::from(low) => {
+                    *length += 1;
+                }
+                _ => runs.push((low, 0)),
+            }
+        }
+        let run_bytes = 2 + 4 * runs.len();
+        let other_bytes = if len <= ARRAY_MAX_LEN { 2 * len } else { 8 * BITMAP_WORDS };
+        if run_bytes < other_bytes {
+            Self::Run { runs, len }
+        } else {
+            self.without_runs()
+        }
+    }
+
+    fn combine(&self, other: &Self, operation: SetOperation) -> Self {
+        if let (Self::Array(values), Self::Array(other_values)) = (self, other) {
+            return Self::Array(merge_sorted(values, other_values, operation)).optimized()
+        }
+        let mut words = self.to_bitmap();
+        let other_words = other.to_bitmap();
+        for (word, other_word) in words.iter_mut().zip(other_words.iter()) {
+            *word = match operation {
+                SetOperation::Union => *word | other_word,
+                SetOperation::Intersection => *word & other_word,
+                SetOperation::Difference => *word & !other_word,
+                SetOperation::SymmetricDifference => *word ^ other_word,
+            };
+        }
+        Self::bitmap(words).optimized()
+    }
+
+    #[inline]
+    fn serialized_len(&self) -> usize {
+        match self {
+            Self::Array(values) => 2 * values.len(),
+            Self::Bitmap { .. } => 8 * BITMAP_WORDS,
+            Self::Run { runs, .. } => 2 + 4 * runs.len(),
+        }
+    }
+
+    fn serialize_into(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Array(values) => {
+                for value in values {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            Self::Bitmap { words, .. } => {
+                for word in words.iter() {
+                    bytes.extend_from_slice(&word.to_le_bytes());
+                }
+            }
+            Self::Run { runs, .. } => {
+                bytes.extend_from_slice(&(runs.len() as u16).to_le_bytes());
+                for (start, length) in runs {
+                    bytes.extend_from_slice(&start.to_le_bytes());
+                    bytes.extend_from_slice(&length.to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// Merges two sorted, deduplicated slices.
+fn merge_sorted(lhs: &[u16], rhs: &[u16], operation: SetOperation) -> Vec<u16> {
+    let mut merged = Vec::with_capacity(lhs.len() + rhs.len());
+    let (mut lhs_index, mut rhs_index) = (0, 0);
+    while lhs_index < lhs.len() || rhs_index < rhs.len() {
+        let lhs_value = lhs.get(lhs_index);
+        let rhs_value = rhs.get(rhs_index);
+        let (value, in_lhs, in_rhs) = match (lhs_value, rhs_value) {
+            (Some(&lhs_value), Some(&rhs_value)) if lhs_value == rhs_value => (lhs_value, true, true),
+            (Some(&lhs_value), Some(&rhs_value)) if lhs_value < rhs_value => (lhs_value, true, false),
+            (Some(&lhs_value), None) => (lhs_value, true, false),
+            (_, Some(&rhs_value)) => (rhs_value, false, true),
+            (None, None) => unreachable!(),
+        };
+        lhs_index += usize::from(in_lhs);
+        rhs_index += usize::from(in_rhs);
+        let keep = match operation {
+            SetOperation::Union => true,
+            SetOperation::Intersection => in_lhs && in_rhs,
+            SetOperation::Difference => in_lhs && !in_rhs,
+            SetOperation::SymmetricDifference => in_lhs != in_rhs,
+        };
+        if keep {
+            merged.push(value);
+        }
+    }
+    merged
+}
+
+#[derive(Debug, Clone)]
+enum ContainerIter<'a> {
+    Array(slice::Iter<'a, u16>),
+    Bitmap {
+        words: &'a [u64; BITMAP_WORDS],
+        word_index: usize,
+        word: u64,
+    },
+    Run {
+        runs: slice::Iter<'a, (u16, u16)>,
+        next: u32,
+        end: u32,
+    },
+}
+
+impl Iterator for ContainerIter<'_> {
+    type Item = u16;
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        match self {
+            Self::Array(values) => values.next().copied(),
+            Self::Bitmap {
+                words,
+                word_index,
+                word,
+            } => {
+                while *word == 0 {
+                    *word_index += 1;
+                    *word = *words.get(*word_index)?;
+                }
+                let low = (*word_index * 64) as u32 + word.trailing_zeros();
+                *word &= *word - 1;
+                Some(low as u16)
+            }
+            Self::Run { runs, next, end } => {
+                if *next == *end {
+                    let &(start, length) = runs.next()?;
+                    *next = u32::from(start);
+                    *end = u32::from(start) + u32::from(length) + 1;
+                }
+                *next += 1;
+                Some((*next - 1) as u16)
+            }
+        }
+    }
+}
+
+/// A compressed set of `u32` values in the Roaring layout.
+///
+/// Values are grouped by their high 16 bits.  Each group keeps its low 16
+/// bits in a sorted array, a 65536 bit bitmap or a list of runs, whichever
+/// suits it.  Results of set operations and `run_optimize()` use whichever
+/// container serializes smallest.
+#[derive(Debug, Default, Clone)]
+pub struct RoaringBitmap {
+    containers: Vec<(u16, Container)>,
+}
+
+impl RoaringBitmap {
+    #[inline]
+    #[must_use] pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of members.
+    #[inline]
+    #[must_use] pub fn len(&self) -> u64 {
+        self.containers
+            .iter()
+            .map(|(_, container)| container.len() as u64)
+            .sum()
+    }
+
+    #[inline]
+    #[must_use] pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.containers.clear();
+    }
+
+    #[inline]
+    #[must_use] pub fn contains(&self, value: u32) -> bool {
+        let (key, low) = split(value);
+        self.container_index(key)
+            .map_or(false, |index| self.containers[index].1.contains(low))
+    }
+
+    /// Adds `value`, returning whether it was not already a member.
+    #[inline]
+    pub fn insert(&mut self, value: u32) -> bool {
+        let (key, low) = split(value);
+        let index = match self.container_index(key) {
+            Ok(index) => index,
+            Err(index) => {
+                self.containers.insert(index, (key, Container::Array(Vec::new())));
+                index
+            }
+        };
+        self.containers[index].1.insert(low)
+    }
+
+    /// Removes `value`, returning whether it was a member.
+    #[inline]
+    pub fn remove(&mut self, value: u32) -> bool {
+        let (key, low) = split(value);
+        let index = match self.container_index(key) {
+            Ok(index) => index,
+            Err(_) => return false,
+        };
+        let removed = self.containers[index].1.remove(low);
+        if self.containers[index].1.len() == 0 {
+            self.containers.remove(index);
+        }
+        removed
+    }
+
+    /// Members in increasing order.
+    #[inline]
+    #[must_use] pub fn iter(&self) -> RoaringIter<'_> {
+        RoaringIter {
+            containers: self.containers.iter(),
+            current: None,
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn min(&self) -> Option<u32> {
+        self.iter().next()
+    }
+
+    #[inline]
+    #[must_use] pub fn max(&self) -> Option<u32> {
+        let (key, container) = self.containers.last()?;
+        container.iter().last().map(|low| join(*key, low))
+    }
+
+    /// Converts every container to whichever form serializes smallest,
+    /// turning long stretches of members into run containers.
+    #[inline]
+    pub fn run_optimize(&mut self) {
+        for (_, container) in &mut self.containers {
+            *container = container.optimized();
+        }
+    }
+
+    #[inline]
+    #[must_use] pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, SetOperation::Union)
+    }
+
+    #[inline]
+    #[must_use] pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, SetOperation::Intersection)
+    }
+
+    #[inline]
+    #[must_use] pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, SetOperation::Difference)
+    }
+
+    #[inline]
+    #[must_use] pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, SetOperation::SymmetricDifference)
+    }
+
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        *self = self.union(other);
+    }
+
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        *self = self.intersection(other);
+    }
+
+    #[inline]
+    pub fn difference_with(&mut self, other: &Self) {
+        *self = self.difference(other);
+    }
+
+    #[inline]
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        *self = self.symmetric_difference(other);
+    }
+
+    #[must_use] pub fn is_subset(&self, other: &Self) -> bool {
+        self.containers.iter().all(|(key, container)| {
+            other.container_index(*key).map_or(false, |index| {
+                container
+                    .combine(&other.containers[index].1, SetOperation::Difference)
+                    .len()
+                    == 0
+            })
+        })
+    }
+
+    #[inline]
+    #[must_use] pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    #[inline]
+    #[must_use] pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    /// A dense `Bits` with bit `value` set for every member, as long as
+    /// needed to hold the largest member.
+    #[must_use] pub fn to_bits(&self) -> Bits {
+        let len = self.max().map_or(0, |max| max as usize + 1);
+        let mut bits = Bits::zeroed(len);
+        for value in self.iter() {
+            bits.set(value as usize, Bit(true));
+        }
+        bits
+    }
+
+    /// Writes the Roaring portable serialization shared with the C, Java,
+    /// Go and Rust Roaring implementations.
+    #[must_use] pub fn serialize(&self) -> Vec<u8> {
+        let container_count = self.containers.len();
+        let has_runs = self
+            .containers
+            .iter()
+            .any(|(_, container)| matches!(container, Container::Run { .. }));
+        let mut bytes = Vec::new();
+        if has_runs {
+            bytes.extend_from_slice(
+                &(SERIAL_COOKIE | ((container_count as u32 - 1) << 16)).to_le_bytes(),
+            );
+            let mut run_flags = vec![0u8; (container_count + 7) / 8];
+            for (index, (_, container)) in self.containers.iter().enumerate() {
+                if let Container::Run { .. } = container {
+                    run_flags[index / 8] |= 1 << (index % 8);
+                }
+            }
+            bytes.extend_from_slice(&run_flags);
+        } else {
+            bytes.extend_from_slice(&SERIAL_COOKIE_NO_RUN_CONTAINER.to_le_bytes());
+            bytes.extend_from_slice(&(container_count as u32).to_le_bytes());
+        }
+        for (key, container) in &self.containers {
+            bytes.extend_from_slice(&key.to_le_bytes());
+            bytes.extend_from_slice(&((container.len() - 1) as u16).to_le_bytes());
+        }
+        if !has_runs || container_count >= NO_OFFSET_THRESHOLD {
+            let mut offset = bytes.len() + 4 * container_count;
+            for (_, container) in &self.containers {
+                bytes.extend_from_slice(&(offset as u32).to_le_bytes());
+                offset += container.serialized_len();
+            }
+        }
+        for (_, container) in &self.containers {
+            container.serialize_into(&mut bytes);
+        }
+        bytes
+    }
+
+    /// Reads the Roaring portable serialization.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, InvalidRoaringFormat> {
+        let mut reader = ByteReader { bytes, position: 0 };
+        let cookie = reader.read_u32()?;
+        let (container_count, run_flags) = if cookie & 0xFFFF == SERIAL_COOKIE {
+            let container_count = (cookie >> 16) as usize + 1;
+            let run_flags = reader.read_bytes((container_count + 7) / 8)?;
+            (container_count, Some(run_flags))
+        } else if cookie == SERIAL_COOKIE_NO_RUN_CONTAINER {
+            (reader.read_u32()? as usize, None)
+        } else {
+            return Err(InvalidRoaringFormat {
+                reason: "unknown cookie",
+            })
+        };
+        let header = reader.read_bytes(4 * container_count)?;
+        if run_flags.is_none() || container_count >= NO_OFFSET_THRESHOLD {
+            reader.read_bytes(4 * container_count)?;
+        }
+
+        let mut containers: Vec<(u16, Container)> = Vec::with_capacity(container_count);
+        for (index, entry) in header.chunks_exact(4).enumerate() {
+            let key = u16::from_le_bytes([entry[0], entry[1]]);
+            let len = usize::from(u16::from_le_bytes([entry[2], entry[3]])) + 1;
+            if containers.last().map_or(false, |&(last_key, _)| last_key >= key) {
+                return Err(InvalidRoaringFormat {
+                    reason: "container keys are not increasing",
+                })
+            }
+            let is_run = run_flags.map_or(false, |flags| flags[index / 8] & (1 << (index % 8)) != 0);
+            let container = if is_run {
+                let run_count = usize::from(reader.read_u16()?);
+                let mut runs = Vec::with_capacity(run_count);
+                let mut next_start = 0u32;
+                for _ in 0..run_count {
+                    let start = reader.read_u16()?;
+                    let length = reader.read_u16()?;
+                    if u32::from(start) < next_start || u32::from(start) + u32::from(length) > 0xFFFF {
+                        return Err(InvalidRoaringFormat {
+                            reason: "runs overlap or overflow",
+                        })
+                    }
+                    next_start = u32::from(start) + u32::from(length) + 1;
+                    runs.push((start, length));
+                }
+                Container::runs(runs)
+            } else if len <= ARRAY_MAX_LEN {
+                let mut values = Vec::with_capacity(len);
+                for _ in 0..len {
+                    values.push(reader.read_u16()?);
+                }
+                if values.windows(2).any(|pair| pair[0] >= pair[1]) {
+                    return Err(InvalidRoaringFormat {
+                        reason: "array values are not increasing",
+                    })
+                }
+                Container::Array(values)
+            } else {
+                let mut words = Box::new([0u64; BITMAP_WORDS]);
+                for word in words.iter_mut() {
+                    *word = reader.read_u64()?;
+                }
+                Container::bitmap(words)
+            };
+            if container.len() != len {
+                return Err(InvalidRoaringFormat {
+                    reason: "container length does not match its header",
+                })
+            }
+            containers.push((key, container));
+        }
+        if reader.position != bytes.len() {
+            return Err(InvalidRoaringFormat {
+                reason: "trailing bytes",
+            })
+        }
+        Ok(Self { containers })
+    }
+
+    #[inline]
+    fn container_index(&self, key: u16) -> Result<usize, usize> {
+        self.containers.binary_search_by_key(&key, |&(container_key, _)| container_key)
+    }
+
+    fn combine(&self, other: &Self, operation: SetOperation) -> Self {
+        let mut containers = Vec::new();
+        let (mut lhs_index, mut rhs_index) = (0, 0);
+        while lhs_index < self.containers.len() || rhs_index < other.containers.len() {
+            let lhs = self.containers.get(lhs_index);
+            let rhs = other.containers.get(rhs_index);
+            let (key, combined) = match (lhs, rhs) {
+                (Some((lhs_key, lhs_container)), Some((rhs_key, rhs_container))) if lhs_key == rhs_key => {
+                    lhs_index += 1;
+                    rhs_index += 1;
+                    (*lhs_key, Some(lhs_container.combine(rhs_container, operation)))
+                }
+                (Some((lhs_key, lhs_container)), Some((rhs_key, _))) if lhs_key < rhs_key => {
+                    lhs_index += 1;
+                    let keep = operation != SetOperation::Intersection;
+                    (*lhs_key, keep.then(|| lhs_container.clone()))
+                }
+                (Some((lhs_key, lhs_container)), None) => {
+                    lhs_index += 1;
+                    let keep = operation != SetOperation::Intersection;
+                    (*lhs_key, keep.then(|| lhs_container.clone()))
+                }
+                (_, Some((rhs_key, rhs_container))) => {
+                    rhs_index += 1;
+                    let keep = matches!(
+                        operation,
+                        SetOperation::Union | SetOperation::SymmetricDifference
+                    );
+                    (*rhs_key, keep.then(|| rhs_container.clone()))
+                }
+                (None, None) => unreachable!(),
+            };
+            if let Some(container) = combined {
+                if container.len() > 0 {
+                    containers.push((key, container));
+                }
+            }
+        }
+        Self { containers }
+    }
+}
+
+#[inline]
+fn split(value: u32) -> (u16, u16) {
+    ((value >> 16) as u16, value as u16)
+}
+
+#[inline]
+fn join(key: u16, low: u16) -> u32 {
+    (u32::from(key) << 16) | u32::from(low)
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    #[inline]
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], InvalidRoaringFormat> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + len)
+            .ok_or(InvalidRoaringFormat { reason: "truncated" })?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_u16(&mut self) -> Result<u16, InvalidRoaringFormat> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    #[inline]
+    fn read_u32(&mut self) -> Result<u32, InvalidRoaringFormat> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    #[inline]
+    fn read_u64(&mut self) -> Result<u64, InvalidRoaringFormat> {
+        Ok(u64::from(self.read_u32()?) | (u64::from(self.read_u32()?) << 32))
+    }
+}
+
+impl From<&Bits> for RoaringBitmap {
+    /// Every set bit becomes a member.  The `Bits` may be at most 2^32 long.
+    fn from(bits: &Bits) -> Self {
+        assert!(
+            bits.len() as u64 <= 1 << 32,
+            "Bits longer than 2^32 do not fit in a RoaringBitmap"
+        );
+        let mut containers = Vec::new();
+        for (key, chunk) in bits.words().chunks(BITMAP_WORDS).enumerate() {
+            let mut words = Box::new([0u64; BITMAP_WORDS]);
+            words[..chunk.len()].copy_from_slice(chunk);
+            let container = Container::bitmap(words);
+            if container.len() > 0 {
+                containers.push((key as u16, container.optimized()));
+            }
+        }
+        Self { containers }
+    }
+}
+
+impl From<&RoaringBitmap> for Bits {
+    #[inline]
+    fn from(roaring_bitmap: &RoaringBitmap) -> Bits {
+        roaring_bitmap.to_bits()
+    }
+}
+
+impl PartialEq for RoaringBitmap {
+    /// Bitmaps are equal when they have the same members, whatever
+    /// containers hold them.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for RoaringBitmap {}
+
+impl FromIterator<u32> for RoaringBitmap {
+    #[inline]
+    fn from_iter<Values: IntoIterator<Item = u32>>(values: Values) -> Self {
+        let mut roaring_bitmap = Self::new();
+        roaring_bitmap.extend(values);
+        roaring_bitmap
+    }
+}
+
+impl Extend<u32> for RoaringBitmap {
+    #[inline]
+    fn extend<Values: IntoIterator<Item = u32>>(&mut self, values: Values) {
+        for value in values {
+            self.insert(value);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a RoaringBitmap {
+    type Item = u32;
+    type IntoIter = RoaringIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl BitOr for &RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> RoaringBitmap {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for &RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> RoaringBitmap {
+        self.intersection(rhs)
+    }
+}
+
+impl BitXor for &RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> RoaringBitmap {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl Sub for &RoaringBitmap {
+    type Output = RoaringBitmap;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> RoaringBitmap {
+        self.difference(rhs)
+    }
+}
+
+/// Iterator over the members of a `RoaringBitmap` in increasing order.
+#[derive(Debug, Clone)]
+pub struct RoaringIter<'a> {
+    containers: slice::Iter<'a, (u16, Container)>,
+    current: Option<(u16, ContainerIter<'a>)>,
+}
+
+impl Iterator for RoaringIter<'_> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<u32> {
+        loop {
+            if let Some((key, container_iter)) = &mut self.current {
+                if let Some(low) = container_iter.next() {
+                    return Some(join(*key, low))
+                }
+            }
+            let (key, container) = self.containers.next()?;
+            self.current = Some((*key, container.iter()));
+        }
+    }
+}
+
+impl FusedIterator for RoaringIter<'_> {}
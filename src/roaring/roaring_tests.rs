@@ -0,0 +1,166 @@
+use crate as bitman;
+use bitman::{Bit, Bits, RoaringBitmap};
+use proptest::prelude::*;
+use roaring::RoaringBitmap as ReferenceBitmap;
+use std::collections::BTreeSet;
+
+fn reference_of(roaring_bitmap: &RoaringBitmap) -> ReferenceBitmap {
+    roaring_bitmap.iter().collect()
+}
+
+#[test]
+fn insert_and_remove_across_containers() {
+    let mut roaring_bitmap = RoaringBitmap::new();
+    assert!(roaring_bitmap.is_empty());
+    assert!(roaring_bitmap.insert(7));
+    assert!(!roaring_bitmap.insert(7));
+    assert!(roaring_bitmap.insert(u32::MAX));
+    assert!(roaring_bitmap.insert(1 << 20));
+    assert_eq!(roaring_bitmap.len(), 3);
+    assert!(roaring_bitmap.contains(1 << 20));
+    assert!(!roaring_bitmap.contains(8));
+    assert_eq!(roaring_bitmap.min(), Some(7));
+    assert_eq!(roaring_bitmap.max(), Some(u32::MAX));
+    assert!(roaring_bitmap.remove(1 << 20));
+    assert!(!roaring_bitmap.remove(1 << 20));
+    assert_eq!(roaring_bitmap.iter().collect::<Vec<u32>>(), vec![7, u32::MAX]);
+    roaring_bitmap.clear();
+    assert!(roaring_bitmap.is_empty());
+}
+
+#[test]
+fn dense_containers_survive_insert_and_remove() {
+    let mut roaring_bitmap: RoaringBitmap = (0..10_000).map(|value| value * 3).collect();
+    assert_eq!(roaring_bitmap.len(), 10_000);
+    for value in 0..6_000 {
+        roaring_bitmap.remove(value * 3);
+    }
+    assert_eq!(roaring_bitmap.len(), 4_000);
+    assert_eq!(roaring_bitmap.min(), Some(18_000));
+    assert!(roaring_bitmap.contains(29_997));
+    assert!(!roaring_bitmap.contains(29_998));
+}
+
+#[test]
+fn run_optimize_keeps_members() {
+    let mut roaring_bitmap: RoaringBitmap = (100..70_000).chain(200_000..200_010).collect();
+    let before = roaring_bitmap.clone();
+    roaring_bitmap.run_optimize();
+    assert_eq!(roaring_bitmap, before);
+    assert!(roaring_bitmap.contains(65_536));
+    assert!(!roaring_bitmap.contains(70_000));
+    assert!(roaring_bitmap.insert(70_000));
+    assert!(roaring_bitmap.remove(500));
+    assert_eq!(roaring_bitmap.len(), before.len());
+}
+
+#[test]
+fn set_operations_with_operators() {
+    let lhs: RoaringBitmap = [1, 2, 3, 1 << 17].into_iter().collect();
+    let rhs: RoaringBitmap = [3, 4, 1 << 18].into_iter().collect();
+    assert_eq!((&lhs | &rhs).iter().collect::<Vec<u32>>(), vec![1, 2, 3, 4, 1 << 17, 1 << 18]);
+    assert_eq!((&lhs & &rhs).iter().collect::<Vec<u32>>(), vec![3]);
+    assert_eq!((&lhs - &rhs).iter().collect::<Vec<u32>>(), vec![1, 2, 1 << 17]);
+    assert_eq!((&lhs ^ &rhs).iter().collect::<Vec<u32>>(), vec![1, 2, 4, 1 << 17, 1 << 18]);
+    assert!(!lhs.is_disjoint(&rhs));
+    assert!((&lhs & &rhs).is_subset(&lhs));
+    assert!(lhs.is_superset(&(&lhs - &rhs)));
+}
+
+#[test]
+fn converts_to_and_from_dense_bits() {
+    let mut bits = Bits::new(&vec![Bit(false); 140_000]);
+    for index in [0, 63, 64, 65_535, 65_536, 139_999] {
+        bits.set(index, Bit(true));
+    }
+    let roaring_bitmap = RoaringBitmap::from(&bits);
+    assert_eq!(roaring_bitmap.iter().collect::<Vec<u32>>(), vec![0, 63, 64, 65_535, 65_536, 139_999]);
+    assert_eq!(Bits::from(&roaring_bitmap), bits);
+    assert_eq!(RoaringBitmap::new().to_bits().len(), 0);
+}
+
+#[test]
+fn serialization_matches_reference_implementation() {
+    let mut roaring_bitmap: RoaringBitmap = (0..5_000)
+        .chain(70_000..70_100)
+        .chain((200_000..260_000).step_by(7))
+        .chain([u32::MAX])
+        .collect();
+    for optimize in [false, true] {
+        if optimize {
+            roaring_bitmap.run_optimize();
+        }
+        let bytes = roaring_bitmap.serialize();
+        let reference = ReferenceBitmap::deserialize_from(&bytes[..]).unwrap();
+        assert_eq!(reference, reference_of(&roaring_bitmap));
+
+        let mut reference_bytes = Vec::new();
+        reference.serialize_into(&mut reference_bytes).unwrap();
+        assert_eq!(RoaringBitmap::deserialize(&reference_bytes).unwrap(), roaring_bitmap);
+        assert_eq!(RoaringBitmap::deserialize(&bytes).unwrap(), roaring_bitmap);
+    }
+}
+
+#[test]
+fn deserialize_reads_reference_bitmap_containers() {
+    let reference: ReferenceBitmap = (10..60_000).chain(1 << 30..(1 << 30) + 3).collect();
+    let mut bytes = Vec::new();
+    reference.serialize_into(&mut bytes).unwrap();
+    let roaring_bitmap = RoaringBitmap::deserialize(&bytes).unwrap();
+    assert_eq!(reference_of(&roaring_bitmap), reference);
+}
+
+#[test]
+fn deserialize_rejects_malformed_input() {
+    assert!(RoaringBitmap::deserialize(&[]).is_err());
+    assert!(RoaringBitmap::deserialize(&[1, 2, 3, 4]).is_err());
+    let bytes: RoaringBitmap = [1, 2, 3].into_iter().collect();
+    let bytes = bytes.serialize();
+    assert!(RoaringBitmap::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(RoaringBitmap::deserialize(&trailing).is_err());
+}
+
+proptest! {
+    #[test]
+    fn matches_btree_set(
+        lhs in proptest::collection::btree_set(0u32..200_000, 0..300),
+        rhs in proptest::collection::btree_set(0u32..200_000, 0..300),
+        lhs_run in 0u32..200_000,
+    ) {
+        let mut lhs: BTreeSet<u32> = lhs;
+        lhs.extend(lhs_run..lhs_run + 5_000);
+        let mut lhs_bitmap: RoaringBitmap = lhs.iter().copied().collect();
+        lhs_bitmap.run_optimize();
+        let rhs_bitmap: RoaringBitmap = rhs.iter().copied().collect();
+        prop_assert_eq!(lhs_bitmap.union(&rhs_bitmap).iter().collect::<BTreeSet<u32>>(), lhs.union(&rhs).copied().collect::<BTreeSet<u32>>());
+        prop_assert_eq!(lhs_bitmap.intersection(&rhs_bitmap).iter().collect::<BTreeSet<u32>>(), lhs.intersection(&rhs).copied().collect::<BTreeSet<u32>>());
+        prop_assert_eq!(lhs_bitmap.difference(&rhs_bitmap).iter().collect::<BTreeSet<u32>>(), lhs.difference(&rhs).copied().collect::<BTreeSet<u32>>());
+        prop_assert_eq!(lhs_bitmap.symmetric_difference(&rhs_bitmap).iter().collect::<BTreeSet<u32>>(), lhs.symmetric_difference(&rhs).copied().collect::<BTreeSet<u32>>());
+        prop_assert_eq!(lhs_bitmap.is_subset(&rhs_bitmap), lhs.is_subset(&rhs));
+        prop_assert_eq!(lhs_bitmap.is_disjoint(&rhs_bitmap), lhs.is_disjoint(&rhs));
+        prop_assert_eq!(lhs_bitmap.len(), lhs.len() as u64);
+        prop_assert_eq!(RoaringBitmap::deserialize(&lhs_bitmap.serialize()).unwrap(), lhs_bitmap);
+    }
+
+    #[test]
+    fn edits_of_every_container_kind_match_btree_set(
+        runs in proptest::collection::vec((0u32..140_000, 1u32..6_000), 1..6),
+        edits in proptest::collection::vec((any::<bool>(), 0u32..150_000), 0..300),
+    ) {
+        let mut reference: BTreeSet<u32> = runs.iter().flat_map(|&(start, len)| start..start + len).collect();
+        let mut roaring_bitmap: RoaringBitmap = reference.iter().copied().collect();
+        roaring_bitmap.run_optimize();
+        for (insert, value) in edits {
+            if insert {
+                prop_assert_eq!(roaring_bitmap.insert(value), reference.insert(value));
+            } else {
+                prop_assert_eq!(roaring_bitmap.remove(value), reference.remove(&value));
+            }
+            prop_assert_eq!(roaring_bitmap.len(), reference.len() as u64);
+        }
+        prop_assert_eq!(roaring_bitmap.iter().collect::<BTreeSet<u32>>(), reference);
+        prop_assert_eq!(RoaringBitmap::deserialize(&roaring_bitmap.serialize()).unwrap(), roaring_bitmap);
+    }
+}
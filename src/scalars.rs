@@ -0,0 +1,204 @@
+use core::{
+    fmt::{self, Display},
+    mem::size_of,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+};
+
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod scalars_tests;
+
+/// Returned when a `Bits` holds a pattern that is not a valid value of the
+/// type it is being converted to.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct InvalidBitPattern {
+    pub type_name: &'static str,
+}
+
+impl Display for InvalidBitPattern {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "Bits do not hold a valid {}", self.type_name)
+    }
+}
+
+/// The value of the last (least significant) 128 bits of `bits`.
+#[inline]
+fn least_significant_value(bits: &Bits) -> u128 {
+    bits.iter()
+        .fold(0u128, |value, bit| (value << 1) | u128::from(bit.0))
+}
+
+/// The value of `bits`, or `None` when a bit above the last `width` is set.
+#[inline]
+fn value_within(bits: &Bits, width: usize) -> Option<u128> {
+    if bits.iter().rev().skip(width).any(|bit| bit.0) {
+        return None
+    }
+    Some(least_significant_value(bits))
+}
+
+impl BitMan for bool {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        1
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        assert_eq!(*index, 0, "Index out of range in call to bit()");
+        Bit(*self)
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        assert_eq!(*index, 0, "Index out of range in call to set_bit()");
+        *self = bit.0;
+    }
+}
+
+impl TryFrom<&Bits> for bool {
+    type Error = InvalidBitPattern;
+
+    /// The last bit, failing when any other bit is set.
+    #[inline]
+    fn try_from(bits_to_convert: &Bits) -> Result<bool, Self::Error> {
+        value_within(bits_to_convert, 1)
+            .map(|value| value == 1)
+            .ok_or(InvalidBitPattern { type_name: "bool" })
+    }
+}
+
+impl BitMan for char {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        32
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        u32::from(*self).bit(index)
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        let mut code_point = u32::from(*self);
+        code_point.set_bit(index, bit);
+        *self = char::from_u32(code_point)
+            .expect("set_bit() would turn a char into an invalid code point");
+    }
+
+    /// Writes every bit before checking the code point, so passing through a
+    /// surrogate on the way to a valid `char` is fine.
+    #[inline]
+    fn set_bits(&mut self, index: u32, bits: &Bits) {
+        let mut code_point = u32::from(*self);
+        code_point.set_bits(index, bits);
+        *self = char::from_u32(code_point)
+            .expect("set_bits() would turn a char into an invalid code point");
+    }
+}
+
+impl TryFrom<&Bits> for char {
+    type Error = InvalidBitPattern;
+
+    #[inline]
+    fn try_from(bits_to_convert: &Bits) -> Result<char, Self::Error> {
+        value_within(bits_to_convert, 32)
+            .and_then(|code_point| char::from_u32(code_point as u32))
+            .ok_or(InvalidBitPattern { type_name: "char" })
+    }
+}
+
+impl<T: BitMan> BitMan for Wrapping<T> {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        self.0.bit_len()
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        self.0.bit(index)
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        self.0.set_bit(index, bit);
+    }
+}
+
+macro_rules! impl_from_bits_for_wrapping {
+    ($($integer_type:ty$(,)?)*) => {$(
+        impl From<&Bits> for Wrapping<$integer_type> {
+            #[inline]
+            fn from(bits_to_convert: &Bits) -> Wrapping<$integer_type> {
+                Wrapping(least_significant_value(bits_to_convert) as $integer_type)
+            }
+        }
+    )*}
+}
+
+impl_from_bits_for_wrapping!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_bitman_for_non_zero {
+    ($($non_zero_type:ty => $integer_type:ty$(,)?)*) => {$(
+        impl BitMan for $non_zero_type {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                self.get().bit_len()
+            }
+
+            #[inline]
+            fn bit(&self, index: &u32) -> Bit {
+                self.get().bit(index)
+            }
+
+            #[inline]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                let mut value = self.get();
+                value.set_bit(index, bit);
+                *self = <$non_zero_type>::new(value)
+                    .expect("set_bit() would clear every bit of a non-zero value");
+            }
+
+            /// Writes every bit before checking the value, so passing through
+            /// zero on the way to a non-zero value is fine.
+            #[inline]
+            fn set_bits(&mut self, index: u32, bits: &Bits) {
+                let mut value = self.get();
+                value.set_bits(index, bits);
+                *self = <$non_zero_type>::new(value)
+                    .expect("set_bits() would clear every bit of a non-zero value");
+            }
+        }
+
+        impl TryFrom<&Bits> for $non_zero_type {
+            type Error = InvalidBitPattern;
+
+            #[inline]
+            fn try_from(bits_to_convert: &Bits) -> Result<$non_zero_type, Self::Error> {
+                value_within(bits_to_convert, size_of::<$integer_type>() * 8)
+                    .and_then(|value| <$non_zero_type>::new(value as $integer_type))
+                    .ok_or(InvalidBitPattern { type_name: stringify!($non_zero_type) })
+            }
+        }
+    )*}
+}
+
+impl_bitman_for_non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+);
@@ -0,0 +1,141 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits, InvalidBitPattern};
+use core::num::{NonZeroI16, NonZeroU32, NonZeroU8, Wrapping};
+use proptest::prelude::*;
+
+#[test]
+fn bool_is_a_single_bit() {
+    assert_eq!(true.bit_len(), 1);
+    assert_eq!(true.bits(), Bits::new(&[Bit(true)]));
+    let mut flag = false;
+    flag.set_bit(&0, &Bit(true));
+    assert!(flag);
+    assert_eq!(bool::try_from(&Bits::new(&[Bit(false), Bit(true)])), Ok(true));
+    assert_eq!(bool::try_from(&Bits::new(&[])), Ok(false));
+}
+
+#[test]
+fn bool_try_from_bits_rejects_other_set_bits() {
+    assert_eq!(
+        bool::try_from(&Bits::new(&[Bit(true), Bit(false)])),
+        Err(InvalidBitPattern { type_name: "bool" })
+    );
+    assert!(bool::try_from(&2u8.bits()).is_err());
+}
+
+#[test]
+#[should_panic]
+fn bool_bit_beyond_index_zero_panics() {
+    let _ = true.bit(&1);
+}
+
+#[test]
+fn char_bits_are_its_code_point() {
+    assert_eq!('A'.bits(), 0x41u32.bits());
+    let mut letter = 'A';
+    letter.set_bit(&26, &Bit(true));
+    assert_eq!(letter, 'a');
+}
+
+#[test]
+fn char_try_from_bits_rejects_surrogates_and_out_of_range() {
+    assert_eq!(char::try_from(&0x1F600u32.bits()), Ok('😀'));
+    assert_eq!(char::try_from(&0x41u8.bits()), Ok('A'));
+    assert_eq!(
+        char::try_from(&0xD800u32.bits()),
+        Err(InvalidBitPattern { type_name: "char" })
+    );
+    assert!(char::try_from(&0x11_0000u32.bits()).is_err());
+}
+
+#[test]
+fn char_try_from_bits_rejects_bits_above_32() {
+    assert_eq!(char::try_from(&0x41u64.bits()), Ok('A'));
+    assert_eq!(
+        char::try_from(&0x1_0000_0041u64.bits()),
+        Err(InvalidBitPattern { type_name: "char" })
+    );
+}
+
+#[test]
+fn char_set_bits_may_pass_through_a_surrogate() {
+    let mut letter = '\u{0800}';
+    letter.set_bits(16, &0xD000u16.bits());
+    assert_eq!(letter, '\u{D000}');
+}
+
+#[test]
+#[should_panic]
+fn char_set_bit_to_surrogate_panics() {
+    let mut letter = '\u{C800}';
+    letter.set_bit(&19, &Bit(true));
+}
+
+#[test]
+fn non_zero_bits_match_inner_value() {
+    let id = NonZeroU32::new(0xDEAD_BEEF).unwrap();
+    assert_eq!(id.bits(), 0xDEAD_BEEFu32.bits());
+    assert_eq!(NonZeroU32::try_from(&id.bits()), Ok(id));
+    let negative = NonZeroI16::new(-2).unwrap();
+    assert_eq!(NonZeroI16::try_from(&negative.bits()), Ok(negative));
+}
+
+#[test]
+fn non_zero_try_from_zero_bits_fails() {
+    assert_eq!(
+        NonZeroU8::try_from(&0u8.bits()),
+        Err(InvalidBitPattern { type_name: "NonZeroU8" })
+    );
+}
+
+#[test]
+fn non_zero_try_from_bits_rejects_bits_above_width() {
+    assert_eq!(NonZeroU8::try_from(&0x0001u16.bits()), Ok(NonZeroU8::new(1).unwrap()));
+    assert_eq!(
+        NonZeroU8::try_from(&0x0101u16.bits()),
+        Err(InvalidBitPattern { type_name: "NonZeroU8" })
+    );
+    assert!(NonZeroI16::try_from(&0x1_0000u32.bits()).is_err());
+}
+
+#[test]
+fn non_zero_set_bits_may_pass_through_zero() {
+    let mut id = NonZeroU8::new(2).unwrap();
+    id.set_bits(0, &1u8.bits());
+    assert_eq!(id.get(), 1);
+}
+
+#[test]
+#[should_panic]
+fn non_zero_set_bits_to_zero_panics() {
+    let mut id = NonZeroU8::new(2).unwrap();
+    id.set_bits(0, &0u8.bits());
+}
+
+#[test]
+#[should_panic]
+fn non_zero_set_bit_to_zero_panics() {
+    let mut id = NonZeroU8::new(1).unwrap();
+    id.set_bit(&7, &Bit(false));
+}
+
+#[test]
+fn wrapping_delegates_to_inner_value() {
+    let mut counter = Wrapping(0u16);
+    counter.set_bit(&15, &Bit(true));
+    assert_eq!(counter, Wrapping(1));
+    assert_eq!(counter.bits(), 1u16.bits());
+    assert_eq!(Wrapping::<u16>::from(&0x1_2345u32.bits()), Wrapping(0x2345));
+}
+
+proptest! {
+    #[test]
+    fn char_round_trips_through_bits(letter in any::<char>()) {
+        prop_assert_eq!(char::try_from(&letter.bits()), Ok(letter));
+    }
+
+    #[test]
+    fn wrapping_round_trips_through_bits(value in any::<i64>()) {
+        prop_assert_eq!(Wrapping::<i64>::from(&Wrapping(value).bits()), Wrapping(value));
+    }
+}
@@ -0,0 +1,205 @@
+use core::ops::RangeBounds;
+
+use crate::{kernels, Bits};
+
+#[cfg(test)]
+mod scanning_tests;
+
+/// Reads the `width` bit field starting at bit `offset` of packed words,
+/// bit `i` living at bit `i % 64` of word `i / 64`.  `width` is at most 64.
+#[inline]
+pub(crate) fn read_field(words: &[u64], offset: usize, width: u32) -> u64 {
+    if width == 0 {
+        return 0
+    }
+    let (word_index, shift) = (offset / 64, (offset % 64) as u32);
+    let mut field = words[word_index] >> shift;
+    if shift + width > 64 {
+        field |= words[word_index + 1] << (64 - shift);
+    }
+    field & field_mask(width)
+}
+
+/// Overwrites the `width` bit field starting at bit `offset` of packed words
+/// with the low `width` bits of `value`.
+#[inline]
+pub(crate) fn write_field(words: &mut [u64], offset: usize, width: u32, value: u64) {
+    if width == 0 {
+        return
+    }
+    let (word_index, shift) = (offset / 64, (offset % 64) as u32);
+    let (mask, value) = (field_mask(width), value & field_mask(width));
+    words[word_index] = (words[word_index] & !(mask << shift)) | (value << shift);
+    if shift + width > 64 {
+        let spilled = 64 - shift;
+        words[word_index + 1] = (words[word_index + 1] & !(mask >> spilled)) | (value >> spilled);
+    }
+}
+
+#[inline]
+fn field_mask(width: u32) -> u64 {
+    u64::MAX >> (64 - width)
+}
+
+/// Mask of the bits of word `word_index` that lie within `start..end`,
+/// where `end` is past the start of the word.
+#[inline]
+fn span_mask(word_index: usize, start: usize, end: usize) -> u64 {
+    let word_start = word_index * 64;
+    let (low, high) = (start.saturating_sub(word_start).min(64), (end - word_start).min(64));
+    if low >= high {
+        return 0
+    }
+    (u64::MAX >> (64 - (high - low))) << low
+}
+
+/// Word `word_index` with a one for every bit within `start..end` that
+/// equals `target`.
+#[inline]
+fn matches(words: &[u64], word_index: usize, start: usize, end: usize, target: bool) -> u64 {
+    let word = if target { words[word_index] } else { !words[word_index] };
+    word & span_mask(word_index, start, end)
+}
+
+/// Index of the first bit within `start..end` of packed words that equals
+/// `target`.  Whole words in between are skipped by the kernels.
+pub(crate) fn find_first(words: &[u64], start: usize, end: usize, target: bool) -> Option<usize> {
+    if start >= end {
+        return None
+    }
+    let (first, last) = (start / 64, (end - 1) / 64);
+    let mut word_index = first;
+    if matches(words, first, start, end, target) == 0 && first != last {
+        let skip = if target { 0 } else { u64::MAX };
+        word_index = kernels::find_first(&words[first + 1..last], skip).map_or(last, |offset| first + 1 + offset);
+    }
+    let word = matches(words, word_index, start, end, target);
+    (word != 0).then(|| word_index * 64 + word.trailing_zeros() as usize)
+}
+
+/// Index of the last bit within `start..end` of packed words that equals
+/// `target`.
+pub(crate) fn find_last(words: &[u64], start: usize, end: usize, target: bool) -> Option<usize> {
+    if start >= end {
+        return None
+    }
+    let (first, last) = (start / 64, (end - 1) / 64);
+    let mut word_index = last;
+    if matches(words, last, start, end, target) == 0 && first != last {
+        let skip = if target { 0 } else { u64::MAX };
+        word_index = kernels::find_last(&words[first + 1..last], skip).map_or(first, |offset| first + 1 + offset);
+    }
+    let word = matches(words, word_index, start, end, target);
+    (word != 0).then(|| word_index * 64 + 63 - word.leading_zeros() as usize)
+}
+
+/// Number of bits within `start..end` of packed words that equal `target`.
+pub(crate) fn count(words: &[u64], start: usize, end: usize, target: bool) -> usize {
+    if start >= end {
+        return 0
+    }
+    let (first, last) = (start / 64, (end - 1) / 64);
+    let mut ones = matches(words, first, start, end, true).count_ones() as usize;
+    if first != last {
+        ones += kernels::count_ones(&words[first + 1..last]);
+        ones += matches(words, last, start, end, true).count_ones() as usize;
+    }
+    if target {
+        ones
+    } else {
+        end - start - ones
+    }
+}
+
+impl Bits {
+    #[inline]
+    #[must_use] pub fn count_ones(&self) -> usize {
+        count(self.words(), 0, self.len(), true)
+    }
+
+    #[inline]
+    #[must_use] pub fn count_zeros(&self) -> usize {
+        self.len() - self.count_ones()
+    }
+
+    #[inline]
+    #[must_use] pub fn count_ones_in<Range: RangeBounds<usize>>(&self, range: Range) -> usize {
+        let (start, end) = self.range_to_bounds(&range);
+        count(self.words(), start, end, true)
+    }
+
+    #[inline]
+    #[must_use] pub fn count_zeros_in<Range: RangeBounds<usize>>(&self, range: Range) -> usize {
+        let (start, end) = self.range_to_bounds(&range);
+        count(self.words(), start, end, false)
+    }
+
+    /// Zeros before the first one, counting from index 0 (the most
+    /// significant end) like `u32::leading_zeros()`.
+    #[inline]
+    #[must_use] pub fn leading_zeros(&self) -> usize {
+        self.first_one().unwrap_or(self.len())
+    }
+
+    #[inline]
+    #[must_use] pub fn leading_ones(&self) -> usize {
+        self.first_zero().unwrap_or(self.len())
+    }
+
+    /// Zeros after the last one, counting back from the last index (the
+    /// least significant end) like `u32::trailing_zeros()`.
+    #[inline]
+    #[must_use] pub fn trailing_zeros(&self) -> usize {
+        self.last_one().map_or(self.len(), |index| self.len() - 1 - index)
+    }
+
+    #[inline]
+    #[must_use] pub fn trailing_ones(&self) -> usize {
+        self.last_zero().map_or(self.len(), |index| self.len() - 1 - index)
+    }
+
+    #[inline]
+    #[must_use] pub fn first_one(&self) -> Option<usize> {
+        find_first(self.words(), 0, self.len(), true)
+    }
+
+    #[inline]
+    #[must_use] pub fn first_zero(&self) -> Option<usize> {
+        find_first(self.words(), 0, self.len(), false)
+    }
+
+    #[inline]
+    #[must_use] pub fn last_one(&self) -> Option<usize> {
+        find_last(self.words(), 0, self.len(), true)
+    }
+
+    #[inline]
+    #[must_use] pub fn last_zero(&self) -> Option<usize> {
+        find_last(self.words(), 0, self.len(), false)
+    }
+
+    #[inline]
+    #[must_use] pub fn next_one_after(&self, index: usize) -> Option<usize> {
+        self.next_after(index, true)
+    }
+
+    #[inline]
+    #[must_use] pub fn next_zero_after(&self, index: usize) -> Option<usize> {
+        self.next_after(index, false)
+    }
+
+    #[inline]
+    #[must_use] pub fn prev_one_before(&self, index: usize) -> Option<usize> {
+        find_last(self.words(), 0, index.min(self.len()), true)
+    }
+
+    #[inline]
+    #[must_use] pub fn prev_zero_before(&self, index: usize) -> Option<usize> {
+        find_last(self.words(), 0, index.min(self.len()), false)
+    }
+
+    #[inline]
+    fn next_after(&self, index: usize, target: bool) -> Option<usize> {
+        find_first(self.words(), index.checked_add(1)?, self.len(), target)
+    }
+}
@@ -0,0 +1,92 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits};
+use proptest::prelude::*;
+
+fn reference_bits(pattern: &[bool]) -> Bits {
+    Bits::new(&pattern.iter().map(|&value| Bit(value)).collect::<Vec<Bit>>())
+}
+
+#[test]
+fn counts_match_primitive_counts() {
+    let value = 0x0F30_0001u32;
+    assert_eq!(value.bits().count_ones(), value.count_ones() as usize);
+    assert_eq!(value.bits().count_zeros(), value.count_zeros() as usize);
+    assert_eq!(value.bits().leading_zeros(), value.leading_zeros() as usize);
+    assert_eq!(value.bits().trailing_zeros(), value.trailing_zeros() as usize);
+    assert_eq!((!value).bits().leading_ones(), (!value).leading_ones() as usize);
+    assert_eq!((!value).bits().trailing_ones(), (!value).trailing_ones() as usize);
+}
+
+#[test]
+fn empty_bits_have_no_ones_or_zeros() {
+    let empty = Bits::new(&[]);
+    assert_eq!(empty.count_ones(), 0);
+    assert_eq!(empty.leading_zeros(), 0);
+    assert_eq!(empty.first_one(), None);
+    assert_eq!(empty.last_zero(), None);
+    assert_eq!(empty.next_one_after(0), None);
+    assert_eq!(empty.prev_zero_before(5), None);
+}
+
+#[test]
+fn all_zero_bits_report_full_length() {
+    let zeros = Bits::new(&[Bit(false); 19]);
+    assert_eq!(zeros.leading_zeros(), 19);
+    assert_eq!(zeros.trailing_zeros(), 19);
+    assert_eq!(zeros.first_zero(), Some(0));
+    assert_eq!(zeros.last_zero(), Some(18));
+    assert_eq!(zeros.first_one(), None);
+}
+
+#[test]
+fn next_and_previous_searches_exclude_start() {
+    let bits = Bits::from_be_bytes(&[0b1001_0000, 0b0000_0001]);
+    assert_eq!(bits.first_one(), Some(0));
+    assert_eq!(bits.next_one_after(0), Some(3));
+    assert_eq!(bits.next_one_after(3), Some(15));
+    assert_eq!(bits.next_one_after(15), None);
+    assert_eq!(bits.next_one_after(usize::MAX), None);
+    assert_eq!(bits.prev_one_before(15), Some(3));
+    assert_eq!(bits.prev_one_before(0), None);
+    assert_eq!(bits.prev_zero_before(4), Some(2));
+    assert_eq!(bits.next_zero_after(14), None);
+}
+
+#[test]
+fn range_counts_only_cover_range() {
+    let bits = Bits::from_be_bytes(&[0xFF, 0x0F]);
+    assert_eq!(bits.count_ones_in(4..12), 4);
+    assert_eq!(bits.count_zeros_in(4..12), 4);
+    assert_eq!(bits.count_ones_in(..), 12);
+    assert_eq!(bits.count_ones_in(12..=15), 4);
+}
+
+#[test]
+#[should_panic]
+fn range_count_beyond_end_panics() {
+    let _ = Bits::from_be_bytes(&[0xFF]).count_ones_in(0..9);
+}
+
+#[test]
+#[should_panic(expected = "beyond the bounds")]
+fn range_count_through_usize_max_panics() {
+    let _ = Bits::from_be_bytes(&[0xFF]).count_ones_in(..=usize::MAX);
+}
+
+proptest! {
+    #[test]
+    fn scans_match_naive_search(pattern in proptest::collection::vec(any::<bool>(), 0..200), index in 0usize..210) {
+        let bits = reference_bits(&pattern);
+        prop_assert_eq!(bits.count_ones(), pattern.iter().filter(|&&value| value).count());
+        prop_assert_eq!(bits.first_one(), pattern.iter().position(|&value| value));
+        prop_assert_eq!(bits.last_zero(), pattern.iter().rposition(|&value| !value));
+        prop_assert_eq!(
+            bits.next_one_after(index),
+            (index + 1..pattern.len()).find(|&position| pattern[position])
+        );
+        prop_assert_eq!(
+            bits.prev_zero_before(index),
+            (0..index.min(pattern.len())).rev().find(|&position| !pattern[position])
+        );
+    }
+}
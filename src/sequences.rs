@@ -0,0 +1,162 @@
+use core::mem::size_of;
+extern crate alloc;
+use alloc::vec::Vec;
+
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod sequences_tests;
+
+/// Order in which the elements of an array, slice or `Vec` are laid out in
+/// the resulting bit string.  The bits within each element are always most
+/// significant first.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum ElementOrder {
+    /// The first element holds the most significant bits (index 0 onwards).
+    BigEndian,
+    /// The last element holds the most significant bits (index 0 onwards).
+    LittleEndian,
+}
+
+/// Access to the bits of a sequence of `BitMan` values with a chosen
+/// `ElementOrder`.  The plain `BitMan` methods use `ElementOrder::BigEndian`.
+pub trait BitManElements: BitMan {
+    fn bit_in_order(&self, index: &u32, order: ElementOrder) -> Bit;
+
+    fn set_bit_in_order(&mut self, index: &u32, bit: &Bit, order: ElementOrder);
+
+    #[inline]
+    fn bits_in_order(&self, order: ElementOrder) -> Bits {
+        let mut output_value: Bits = Bits::new(&[]);
+        for current_index in 0..self.bit_len() as u32 {
+            output_value.push(self.bit_in_order(&current_index, order));
+        }
+        output_value
+    }
+
+    #[inline]
+    fn set_bits_in_order(&mut self, mut index: u32, bits: &Bits, order: ElementOrder) {
+        for current_bit in bits.iter() {
+            self.set_bit_in_order(&index, &current_bit, order);
+            index += 1;
+        }
+    }
+}
+
+#[inline]
+fn element_position(
+    index: u32,
+    element_bit_len: usize,
+    element_count: usize,
+    order: ElementOrder,
+) -> (usize, u32) {
+    let element = index as usize / element_bit_len;
+    assert!(
+        element < element_count,
+        "Index {} out of range for a sequence of {} bits",
+        index,
+        element_bit_len * element_count
+    );
+    let offset = (index as usize % element_bit_len) as u32;
+    match order {
+        ElementOrder::BigEndian => (element, offset),
+        ElementOrder::LittleEndian => (element_count - 1 - element, offset),
+    }
+}
+
+macro_rules! impl_bitman_for_sequences {
+    ($($element_type:ty$(,)?)*) => {$(
+        impl BitMan for [$element_type] {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                self.len() * size_of::<$element_type>() * 8
+            }
+
+            #[inline]
+            fn bit(&self, index: &u32) -> Bit {
+                self.bit_in_order(index, ElementOrder::BigEndian)
+            }
+
+            #[inline]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                self.set_bit_in_order(index, bit, ElementOrder::BigEndian);
+            }
+        }
+
+        impl BitManElements for [$element_type] {
+            #[inline]
+            fn bit_in_order(&self, index: &u32, order: ElementOrder) -> Bit {
+                let (element, offset) =
+                    element_position(*index, size_of::<$element_type>() * 8, self.len(), order);
+                self[element].bit(&offset)
+            }
+
+            #[inline]
+            fn set_bit_in_order(&mut self, index: &u32, bit: &Bit, order: ElementOrder) {
+                let (element, offset) =
+                    element_position(*index, size_of::<$element_type>() * 8, self.len(), order);
+                self[element].set_bit(&offset, bit);
+            }
+        }
+
+        impl<const LENGTH: usize> BitMan for [$element_type; LENGTH] {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                self[..].bit_len()
+            }
+
+            #[inline]
+            fn bit(&self, index: &u32) -> Bit {
+                self[..].bit(index)
+            }
+
+            #[inline]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                self[..].set_bit(index, bit);
+            }
+        }
+
+        impl<const LENGTH: usize> BitManElements for [$element_type; LENGTH] {
+            #[inline]
+            fn bit_in_order(&self, index: &u32, order: ElementOrder) -> Bit {
+                self[..].bit_in_order(index, order)
+            }
+
+            #[inline]
+            fn set_bit_in_order(&mut self, index: &u32, bit: &Bit, order: ElementOrder) {
+                self[..].set_bit_in_order(index, bit, order);
+            }
+        }
+
+        impl BitMan for Vec<$element_type> {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                self[..].bit_len()
+            }
+
+            #[inline]
+            fn bit(&self, index: &u32) -> Bit {
+                self[..].bit(index)
+            }
+
+            #[inline]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                self[..].set_bit(index, bit);
+            }
+        }
+
+        impl BitManElements for Vec<$element_type> {
+            #[inline]
+            fn bit_in_order(&self, index: &u32, order: ElementOrder) -> Bit {
+                self[..].bit_in_order(index, order)
+            }
+
+            #[inline]
+            fn set_bit_in_order(&mut self, index: &u32, bit: &Bit, order: ElementOrder) {
+                self[..].set_bit_in_order(index, bit, order);
+            }
+        }
+    )*}
+}
+
+impl_bitman_for_sequences!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
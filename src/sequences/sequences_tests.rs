@@ -0,0 +1,81 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, BitManElements, Bits, ElementOrder};
+use proptest::prelude::*;
+
+#[test]
+fn bit_len_of_array_is_sum_of_element_widths() {
+    assert_eq!([0u32; 4].bit_len(), 128);
+    assert_eq!([0u8; 0].bit_len(), 0);
+}
+
+#[test]
+fn bit_of_array_reads_elements_in_big_endian_order() {
+    let array = [0b1000_0000u8, 0b0000_0001u8];
+    assert_eq!(array.bit(&0), Bit(true));
+    assert_eq!(array.bit(&7), Bit(false));
+    assert_eq!(array.bit(&8), Bit(false));
+    assert_eq!(array.bit(&15), Bit(true));
+}
+
+#[test]
+fn bit_in_order_of_array_reads_elements_in_little_endian_order() {
+    let array = [0b1000_0000u8, 0b0000_0001u8];
+    assert_eq!(array.bit_in_order(&7, ElementOrder::LittleEndian), Bit(true));
+    assert_eq!(array.bit_in_order(&8, ElementOrder::LittleEndian), Bit(true));
+    assert_eq!(array.bit_in_order(&0, ElementOrder::LittleEndian), Bit(false));
+}
+
+#[test]
+fn bits_of_slice_match_bits_from_be_bytes() {
+    let bytes: &[u8] = &[0xA5, 0x0F, 0x3C];
+    assert_eq!(bytes.bits(), Bits::from_be_bytes(bytes));
+}
+
+#[test]
+fn bits_in_little_endian_order_match_bits_from_le_bytes() {
+    let bytes: &[u8] = &[0xA5, 0x0F, 0x3C];
+    assert_eq!(
+        bytes.bits_in_order(ElementOrder::LittleEndian),
+        Bits::from_le_bytes(bytes)
+    );
+}
+
+#[test]
+fn set_bit_on_vec_crosses_element_boundaries() {
+    let mut vector: Vec<u16> = vec![0, 0];
+    vector.set_bit(&15, &Bit(true));
+    vector.set_bit(&16, &Bit(true));
+    assert_eq!(vector, vec![0x0001, 0x8000]);
+    vector.set_bit_in_order(&0, &Bit(true), ElementOrder::LittleEndian);
+    assert_eq!(vector, vec![0x0001, 0x8000 | 0x8000]);
+}
+
+#[test]
+fn set_bits_on_array_writes_across_elements() {
+    let mut array = [0u8; 2];
+    array.set_bits(4, &0xFFu8.bits());
+    assert_eq!(array, [0x0F, 0xF0]);
+}
+
+#[test]
+#[should_panic]
+fn bit_beyond_end_of_array_panics() {
+    let _ = [0u32; 2].bit(&64);
+}
+
+proptest! {
+    #[test]
+    fn bits_then_set_bits_round_trips_any_vec(values in proptest::collection::vec(any::<i32>(), 0..8)) {
+        let bits = values.bits();
+        let mut copy = vec![0i32; values.len()];
+        copy.set_bits(0, &bits);
+        prop_assert_eq!(copy, values);
+    }
+
+    #[test]
+    fn little_endian_order_of_array_is_reversed_elements(values in any::<[u64; 3]>()) {
+        let mut reversed = values;
+        reversed.reverse();
+        prop_assert_eq!(values.bits_in_order(ElementOrder::LittleEndian), reversed.bits());
+    }
+}
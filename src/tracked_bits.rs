@@ -0,0 +1,289 @@
+extern crate alloc;
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Deref};
+
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod tracked_bits_tests;
+
+/// One write: the bits it flipped, from the first to the last bit whose
+/// value changed.  Flipping them again undoes the write, and once more
+/// redoes it.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct Change {
+    start: usize,
+    flips: Bits,
+}
+
+/// A `Bits` that remembers which bits were written since the last commit.
+///
+/// Every `set_bit()`, `set_bits()` and `&=`/`|=`/`^=` marks the bits it
+/// wrote in the dirty mask, whether or not their value changed; `diff()`
+/// gives the bits whose value differs from the last commit.  With
+/// `with_journal()` each write can also be undone and redone; the journal
+/// keeps only the span of bits a write changed, and `set_journal_limit()`
+/// caps how many writes it remembers.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct TrackedBits {
+    bits: Bits,
+    committed: Bits,
+    dirty: Bits,
+    journaling: bool,
+    journal_limit: usize,
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+}
+
+impl TrackedBits {
+    /// Tracks `bits`, treating their current value as committed.
+    #[inline]
+    #[must_use] pub fn new(bits: Bits) -> Self {
+        Self {
+            committed: bits.clone(),
+            dirty: Bits::new(&vec![Bit(false); bits.len()]),
+            bits,
+            journaling: false,
+            journal_limit: usize::MAX,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Like `new()`, also journaling every write for `undo()` and `redo()`.
+    #[inline]
+    #[must_use] pub fn with_journal(bits: Bits) -> Self {
+        let mut tracked_bits = Self::new(bits);
+        tracked_bits.journaling = true;
+        tracked_bits
+    }
+
+    #[inline]
+    #[must_use] pub fn as_bits(&self) -> &Bits {
+        &self.bits
+    }
+
+    #[inline]
+    #[must_use] pub fn into_bits(self) -> Bits {
+        self.bits
+    }
+
+    /// The value as of the last `commit()`.
+    #[inline]
+    #[must_use] pub fn committed(&self) -> &Bits {
+        &self.committed
+    }
+
+    /// Set for every bit written since the last `commit()` or
+    /// `clear_dirty()`.
+    #[inline]
+    #[must_use] pub fn dirty_mask(&self) -> &Bits {
+        &self.dirty
+    }
+
+    #[inline]
+    #[must_use] pub fn is_dirty(&self) -> bool {
+        self.dirty.first_one().is_some()
+    }
+
+    /// Set for every bit whose value differs from the last `commit()`.
+    #[inline]
+    #[must_use] pub fn diff(&self) -> Bits {
+        self.bits.clone() ^ self.committed.clone()
+    }
+
+    /// Forgets which bits were written, keeping the committed value.
+    #[inline]
+    pub fn clear_dirty(&mut self) {
+        self.dirty = Bits::new(&vec![Bit(false); self.bits.len()]);
+    }
+
+    /// Makes the current value the committed one, returning the dirty mask
+    /// it replaces.  The journal is kept.
+    #[inline]
+    pub fn commit(&mut self) -> Bits {
+        self.committed = self.bits.clone();
+        let dirty = self.dirty.clone();
+        self.clear_dirty();
+        dirty
+    }
+
+    /// Writes the committed value back over every bit, as one journaled
+    /// change.
+    #[inline]
+    pub fn revert(&mut self) {
+        let committed = self.committed.clone();
+        self.write(0, &committed);
+    }
+
+    #[inline]
+    #[must_use] pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    #[inline]
+    #[must_use] pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverses the latest journaled write, returning whether there was
+    /// one.  The restored bits are marked dirty.
+    pub fn undo(&mut self) -> bool {
+        let change = match self.undo.pop_back() {
+            Some(change) => change,
+            None => return false,
+        };
+        self.flip(&change);
+        self.redo.push(change);
+        true
+    }
+
+    /// Reapplies the latest undone write, returning whether there was one.
+    pub fn redo(&mut self) -> bool {
+        let change = match self.redo.pop() {
+            Some(change) => change,
+            None => return false,
+        };
+        self.flip(&change);
+        self.undo.push_back(change);
+        true
+    }
+
+    /// Drops every journaled write.
+    #[inline]
+    pub fn clear_journal(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Most writes the journal remembers for `undo()`.
+    #[inline]
+    #[must_use] pub fn journal_limit(&self) -> usize {
+        self.journal_limit
+    }
+
+    /// Remembers at most `limit` writes for `undo()`, forgetting the oldest
+    /// ones first.  Unlimited by default.
+    #[inline]
+    pub fn set_journal_limit(&mut self, limit: usize) {
+        self.journal_limit = limit;
+        while self.undo.len() > limit {
+            self.undo.pop_front();
+        }
+    }
+
+    fn write(&mut self, start: usize, bits: &Bits) {
+        let end = start + bits.len();
+        assert!(
+            end <= self.bits.len(),
+            "Writing bits {start}..{end} is beyond the bounds of TrackedBits of length {}",
+            self.bits.len()
+        );
+        if self.journaling {
+            let flips = self.bits.slice(start..end) ^ bits.clone();
+            let change = match (flips.first_one(), flips.last_one()) {
+                (Some(first), Some(last)) => Change {
+                    start: start + first,
+                    flips: flips.slice(first..=last),
+                },
+                _ => Change {
+                    start,
+                    flips: Bits::new(&[]),
+                },
+            };
+            self.redo.clear();
+            if self.journal_limit > 0 {
+                if self.undo.len() == self.journal_limit {
+                    self.undo.pop_front();
+                }
+                self.undo.push_back(change);
+            }
+        }
+        self.bits.set_bits(start as u32, bits);
+        for index in start..end {
+            self.dirty.set(index, Bit(true));
+        }
+    }
+
+    /// Applies a journaled change, marking the flipped bits dirty.
+    fn flip(&mut self, change: &Change) {
+        for index in change.flips.iter_ones() {
+            let index = change.start + index;
+            self.bits.set(index, !self.bits[index]);
+            self.dirty.set(index, Bit(true));
+        }
+    }
+}
+
+impl Deref for TrackedBits {
+    type Target = Bits;
+
+    #[inline]
+    fn deref(&self) -> &Bits {
+        &self.bits
+    }
+}
+
+impl From<Bits> for TrackedBits {
+    #[inline]
+    fn from(bits: Bits) -> Self {
+        Self::new(bits)
+    }
+}
+
+impl BitMan for TrackedBits {
+    #[inline]
+    fn bit_len(&self) -> usize {
+        self.bits.len()
+    }
+
+    #[inline]
+    fn bit(&self, index: &u32) -> Bit {
+        self.bits.bit(index)
+    }
+
+    #[inline]
+    fn set_bit(&mut self, index: &u32, bit: &Bit) {
+        self.write(*index as usize, &Bits::new(&[*bit]));
+    }
+
+    #[inline]
+    fn bits(&self) -> Bits {
+        self.bits.clone()
+    }
+
+    /// Writes `bits` as a single journaled change.
+    #[inline]
+    fn set_bits(&mut self, index: u32, bits: &Bits) {
+        self.write(index as usize, bits);
+    }
+}
+
+impl BitAndAssign<Bits> for TrackedBits {
+    /// Writes every bit covered by both sides.
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Bits) {
+        let result = self.bits.clone() & rhs;
+        self.write(0, &result);
+    }
+}
+
+impl BitOrAssign<Bits> for TrackedBits {
+    /// Writes every bit covered by both sides.
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Bits) {
+        let result = self.bits.clone() | rhs;
+        self.write(0, &result);
+    }
+}
+
+impl BitXorAssign<Bits> for TrackedBits {
+    /// Writes every bit covered by both sides.
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Bits) {
+        let result = self.bits.clone() ^ rhs;
+        self.write(0, &result);
+    }
+}
@@ -0,0 +1,137 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits, TrackedBits};
+use proptest::prelude::*;
+
+#[test]
+fn writes_mark_the_dirty_mask() {
+    let mut tracked_bits = TrackedBits::new(Bits::from_be_bytes(&[0b1010_0000]));
+    assert!(!tracked_bits.is_dirty());
+    tracked_bits.set_bit(&0, &Bit(true));
+    tracked_bits.set_bits(4, &Bits::new(&[Bit(true), Bit(true)]));
+    assert_eq!(tracked_bits.dirty_mask(), &Bits::from_be_bytes(&[0b1000_1100]));
+    assert_eq!(tracked_bits.diff(), Bits::from_be_bytes(&[0b0000_1100]));
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b1010_1100]));
+}
+
+#[test]
+fn operator_assignment_marks_the_overlap() {
+    let mut tracked_bits = TrackedBits::new(Bits::from_be_bytes(&[0b1111_0000, 0b0000_1111]));
+    tracked_bits ^= Bits::from_be_bytes(&[0b0000_0001]);
+    assert_eq!(tracked_bits.dirty_mask(), &Bits::from_be_bytes(&[0b1111_1111, 0]));
+    assert_eq!(tracked_bits.diff(), Bits::from_be_bytes(&[0b0000_0001, 0]));
+    tracked_bits |= Bits::from_be_bytes(&[0, 0b1000_0000]);
+    tracked_bits &= Bits::from_be_bytes(&[0b0111_1111, 0b1111_1111]);
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b0111_0001, 0b1000_1111]));
+}
+
+#[test]
+fn commit_resets_the_baseline() {
+    let mut tracked_bits = TrackedBits::new(Bits::from_be_bytes(&[0]));
+    tracked_bits.set_bit(&7, &Bit(true));
+    assert_eq!(tracked_bits.commit(), Bits::from_be_bytes(&[1]));
+    assert!(!tracked_bits.is_dirty());
+    assert_eq!(tracked_bits.committed(), &Bits::from_be_bytes(&[1]));
+    tracked_bits.set_bit(&7, &Bit(true));
+    assert!(tracked_bits.is_dirty());
+    assert_eq!(tracked_bits.diff(), Bits::from_be_bytes(&[0]));
+    tracked_bits.set_bit(&0, &Bit(true));
+    tracked_bits.revert();
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[1]));
+    tracked_bits.clear_dirty();
+    assert!(!tracked_bits.is_dirty());
+}
+
+#[test]
+fn journal_undoes_and_redoes_writes() {
+    let mut tracked_bits = TrackedBits::with_journal(Bits::from_be_bytes(&[0]));
+    assert!(!tracked_bits.undo());
+    tracked_bits.set_bit(&1, &Bit(true));
+    tracked_bits.set_bits(4, &Bits::new(&[Bit(true); 4]));
+    assert!(tracked_bits.undo());
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b0100_0000]));
+    assert!(tracked_bits.can_redo());
+    assert!(tracked_bits.redo());
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b0100_1111]));
+    assert!(tracked_bits.undo());
+    tracked_bits.set_bit(&7, &Bit(true));
+    assert!(!tracked_bits.can_redo());
+    assert!(tracked_bits.undo());
+    assert!(tracked_bits.undo());
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0]));
+    assert!(!tracked_bits.can_undo());
+}
+
+#[test]
+fn journal_limit_forgets_the_oldest_writes() {
+    let mut tracked_bits = TrackedBits::with_journal(Bits::from_be_bytes(&[0, 0]));
+    assert_eq!(tracked_bits.journal_limit(), usize::MAX);
+    for index in 0..6 {
+        tracked_bits.set_bit(&index, &Bit(true));
+    }
+    tracked_bits.set_journal_limit(4);
+    tracked_bits |= Bits::from_be_bytes(&[0, 0b1000_0001]);
+    let mut undone = 0;
+    while tracked_bits.undo() {
+        undone += 1;
+    }
+    assert_eq!(undone, 4);
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b1110_0000, 0]));
+    while tracked_bits.redo() {}
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b1111_1100, 0b1000_0001]));
+    tracked_bits.set_journal_limit(0);
+    assert!(!tracked_bits.can_undo());
+    tracked_bits.set_bit(&15, &Bit(false));
+    assert!(!tracked_bits.undo());
+}
+
+#[test]
+fn whole_buffer_writes_undo_only_the_changed_bits() {
+    let mut tracked_bits = TrackedBits::with_journal(Bits::from_be_bytes(&[0b1010_1010; 32]));
+    tracked_bits ^= Bits::from_be_bytes(&[0b0001_1000; 32]);
+    tracked_bits &= Bits::from_be_bytes(&[0xFF; 32]);
+    tracked_bits.revert();
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b1010_1010; 32]));
+    assert!(tracked_bits.undo());
+    assert!(tracked_bits.undo());
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b1011_0010; 32]));
+    assert!(tracked_bits.undo());
+    assert_eq!(tracked_bits.as_bits(), &Bits::from_be_bytes(&[0b1010_1010; 32]));
+}
+
+#[test]
+fn untracked_journal_is_empty() {
+    let mut tracked_bits = TrackedBits::from(Bits::from_be_bytes(&[0]));
+    tracked_bits.set_bit(&1, &Bit(true));
+    assert!(!tracked_bits.undo());
+    assert_eq!(tracked_bits.into_bits(), Bits::from_be_bytes(&[0b0100_0000]));
+}
+
+#[test]
+#[should_panic(expected = "beyond the bounds")]
+fn rejects_writes_past_the_end() {
+    let mut tracked_bits = TrackedBits::new(Bits::from_be_bytes(&[0]));
+    tracked_bits.set_bits(6, &Bits::from_be_bytes(&[0]));
+}
+
+proptest! {
+    #[test]
+    fn undoing_everything_restores_the_original(
+        initial in any::<u16>(),
+        writes in proptest::collection::vec((0u32..16, any::<bool>()), 0..30),
+    ) {
+        let original = Bits::from_be_bytes(&initial.to_be_bytes());
+        let mut tracked_bits = TrackedBits::with_journal(original.clone());
+        let mut expected_dirty = Bits::new(&[Bit(false); 16]);
+        for &(index, value) in &writes {
+            tracked_bits.set_bit(&index, &Bit(value));
+            expected_dirty.set(index as usize, Bit(true));
+        }
+        prop_assert_eq!(tracked_bits.dirty_mask(), &expected_dirty);
+        prop_assert_eq!(tracked_bits.diff(), tracked_bits.as_bits().clone() ^ original.clone());
+        let latest = tracked_bits.as_bits().clone();
+        while tracked_bits.undo() {}
+        prop_assert_eq!(tracked_bits.as_bits(), &original);
+        while tracked_bits.redo() {}
+        prop_assert_eq!(tracked_bits.as_bits(), &latest);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Bit, BitMan, Bits};
+
+#[cfg(test)]
+mod tuples_tests;
+
+macro_rules! impl_bitman_for_tuples {
+    ($(($($member_type:ident $position:tt),+))*) => {$(
+        impl<$($member_type: BitMan),+> BitMan for ($($member_type,)+) {
+            #[inline]
+            fn bit_len(&self) -> usize {
+                0 $(+ self.$position.bit_len())+
+            }
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn bit(&self, index: &u32) -> Bit {
+                let mut offset = *index;
+                $(
+                    let member_bit_len = self.$position.bit_len() as u32;
+                    if offset < member_bit_len {
+                        return self.$position.bit(&offset)
+                    }
+                    offset -= member_bit_len;
+                )+
+                panic!("Index {} passed to bit() is beyond the bounds of the tuple", index)
+            }
+
+            #[inline]
+            #[allow(unused_assignments)]
+            fn set_bit(&mut self, index: &u32, bit: &Bit) {
+                let mut offset = *index;
+                $(
+                    let member_bit_len = self.$position.bit_len() as u32;
+                    if offset < member_bit_len {
+                        return self.$position.set_bit(&offset, bit)
+                    }
+                    offset -= member_bit_len;
+                )+
+                panic!("Index {} passed to set_bit() is beyond the bounds of the tuple", index)
+            }
+
+            #[inline]
+            fn bits(&self) -> Bits {
+                let mut output_value = Bits::new(&[]);
+                $(
+                    output_value.extend(self.$position.bits().iter());
+                )+
+                output_value
+            }
+
+            /// Hands each member its share of `bits` in one call, so members
+            /// that validate their value only see the finished pattern.
+            #[inline]
+            #[allow(unused_assignments)]
+            fn set_bits(&mut self, index: u32, bits: &Bits) {
+                let (start, end) = (index as usize, index as usize + bits.len());
+                assert!(end <= self.bit_len(), "Bits passed to set_bits() run beyond the bounds of the tuple");
+                let mut member_start = 0;
+                $(
+                    let member_end = member_start + self.$position.bit_len();
+                    if start < member_end && member_start < end {
+                        let (from, to) = (start.max(member_start), end.min(member_end));
+                        self.$position.set_bits(
+                            (from - member_start) as u32,
+                            &bits.slice(from - start..to - start),
+                        );
+                    }
+                    member_start = member_end;
+                )+
+            }
+        }
+    )*}
+}
+
+impl_bitman_for_tuples!(
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+);
@@ -0,0 +1,71 @@
+use crate as bitman;
+use bitman::{Bit, BitMan, Bits};
+use core::num::NonZeroU8;
+use proptest::prelude::*;
+
+#[test]
+fn bit_len_of_tuple_is_sum_of_members() {
+    assert_eq!((0u8,).bit_len(), 8);
+    assert_eq!((0u8, 0u16, true).bit_len(), 25);
+}
+
+#[test]
+fn bits_of_tuple_concatenate_members_in_order() {
+    let header = (0xABu8, 0x1234u16);
+    assert_eq!(header.bits(), Bits::from_be_bytes(&[0xAB, 0x12, 0x34]));
+}
+
+#[test]
+fn bit_of_tuple_crosses_member_boundaries() {
+    let header = (true, 0x01u8, false);
+    assert_eq!(header.bit(&0), Bit(true));
+    assert_eq!(header.bit(&8), Bit(true));
+    assert_eq!(header.bit(&9), Bit(false));
+}
+
+#[test]
+fn set_bits_splits_bits_back_into_members() {
+    let mut header = (0u8, 0u16, 0i8, false);
+    let mut bits = Bits::from_be_bytes(&[0xAB, 0x12, 0x34, 0xFF]);
+    bits.push(Bit(true));
+    header.set_bits(0, &bits);
+    assert_eq!(header, (0xAB, 0x1234, -1, true));
+}
+
+#[test]
+fn set_bits_validates_each_member_once() {
+    let mut record = (NonZeroU8::new(2).unwrap(), '\u{0800}');
+    let mut bits = 1u8.bits();
+    bits.extend(0xD000u32.bits().iter());
+    record.set_bits(0, &bits);
+    assert_eq!(record, (NonZeroU8::new(1).unwrap(), '\u{D000}'));
+    record.set_bits(4, &Bits::new(&[Bit(true); 4]));
+    assert_eq!(record.0.get(), 0x0F);
+}
+
+#[test]
+#[should_panic]
+fn set_bits_beyond_end_of_tuple_panics() {
+    (0u8, 0u8).set_bits(12, &0u8.bits());
+}
+
+#[test]
+fn nested_tuples_flatten() {
+    let nested = ((1u8, 2u8), [3u8, 4u8]);
+    assert_eq!(nested.bits(), Bits::from_be_bytes(&[1, 2, 3, 4]));
+}
+
+#[test]
+#[should_panic]
+fn bit_beyond_end_of_tuple_panics() {
+    let _ = (0u8, 0u8).bit(&16);
+}
+
+proptest! {
+    #[test]
+    fn tuple_round_trips_through_bits(value in any::<(u8, i32, bool, u64)>()) {
+        let mut copy = (0u8, 0i32, false, 0u64);
+        copy.set_bits(0, &value.bits());
+        prop_assert_eq!(copy, value);
+    }
+}